@@ -1,10 +1,12 @@
 #[allow(dead_code)]
 use super::fb::FB;
-use super::fb::Color;
-use image::{DynamicImage, Rgba }; // rgba is used internally by rusttype and image
+use super::fb::{Color, Paint};
+use image::{DynamicImage, GenericImageView, Rgba }; // rgba is used internally by rusttype and image
 use glyph_brush_layout::*;
 use ab_glyph::*;
 use glyph_brush_draw_cache::{DrawCache, Rectangle};
+use std::sync::Arc;
+use std::collections::HashMap;
 // Layer
 use log::*;
 use log::Level::*;
@@ -24,10 +26,42 @@ impl<T> Layer<T> {
     }
 }
 
+// Viewport
+// a window onto layer groups larger than it
+// the groups are drawn shifted by the scroll offset and clipped to x, y, w, h
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub scroll_x: i32,
+    pub scroll_y: i32
+}
+
+impl Viewport {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Viewport {
+        Viewport {x, y, w, h, scroll_x: 0, scroll_y: 0}
+    }
+
+    pub fn scrolled(mut self, scroll_x: i32, scroll_y: i32) -> Viewport {
+        self.scroll_x = scroll_x;
+        self.scroll_y = scroll_y;
+        self
+    }
+
+    fn clip(&self) -> (u32, u32, u32, u32) {
+        let x = self.x.max(0);
+        let y = self.y.max(0);
+        (x as u32, y as u32, (self.x + self.w - x).max(0) as u32, (self.y + self.h - y).max(0) as u32)
+    }
+}
+
 // Canvas
 pub struct Canvas {
     screen: FB,
-    pub layers: Vec<Layer<Box<dyn Draw + Send>>>
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    viewports: HashMap<String, Viewport>
 }
 
 impl Canvas {
@@ -36,15 +70,26 @@ impl Canvas {
         let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
         Canvas {
             screen: fb,
-            layers    
+            layers,
+            viewports: HashMap::new()
         }
     }
 
     pub fn render(&mut self) {
         self.screen.clear();
-        for layer in &self.layers {
+        for layer in self.layers.iter_mut() {
             if layer.active{
-                layer.item.draw(&mut self.screen);
+                match self.viewports.get(&layer.group) {
+                    Some(viewport) => {
+                        // slid over for the draw only, the layers keep their own coordinates
+                        layer.item.slide(-viewport.scroll_x, -viewport.scroll_y);
+                        self.screen.set_clip(Some(viewport.clip()));
+                        layer.item.draw(&mut self.screen);
+                        self.screen.set_clip(None);
+                        layer.item.slide(viewport.scroll_x, viewport.scroll_y);
+                    },
+                    None => layer.item.draw(&mut self.screen)
+                }
                  if log_enabled!(Trace) {
                     if let Some(text) = layer.item.get_text() {
                         trace!("Rendering layer with text {}", text);
@@ -56,6 +101,20 @@ impl Canvas {
 
     }
 
+    // screen width and height in pixels
+    pub fn size(&self) -> (i32, i32) {
+        (self.screen.w as i32, self.screen.h as i32)
+    }
+
+    // draw a group through a viewport, replacing any viewport it had
+    pub fn set_viewport(&mut self, group: String, viewport: Viewport) {
+        self.viewports.insert(group, viewport);
+    }
+
+    pub fn clear_viewport(&mut self, group: String) {
+        self.viewports.remove(&group);
+    }
+
     #[allow(dead_code)]
     pub fn clear(&mut self){
         self.screen.clear();
@@ -101,6 +160,26 @@ impl Canvas {
         }
     }
 
+    // swap a group's layers for new ones, keeping its place in the draw order
+    // a group not yet on the canvas is added on top
+    pub fn replace_layer_group(&mut self, group: String, mut layers: Vec<Layer<Box<dyn Draw + Send>>>) {
+        match self.layers.iter().position(|layer| layer.group == group) {
+            Some(position) => {
+                self.drop_layer_group(group);
+                let tail = self.layers.split_off(position);
+                self.layers.append(&mut layers);
+                self.layers.extend(tail);
+            },
+            None => self.layers.append(&mut layers)
+        }
+    }
+
+    // move a group to the top of the draw order
+    pub fn raise_layer_group(&mut self, group: String) {
+        let mut layers = self.get_layer_group(group);
+        self.layers.append(&mut layers);
+    }
+
     pub fn get_layer_group(&mut self, group: String) ->  Vec<Layer<Box<dyn Draw + Send>>>  {
         let mut to_return: Vec<usize> = vec![];
         let mut result: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
@@ -143,12 +222,56 @@ pub struct Rect {
     pub w: i32,
     pub h: i32,
     pub filled: bool,
-    pub color: Color
+    pub paint: Paint,
+    // corner radius and outline thickness
+    pub radius: i32,
+    pub border: i32
 }
 
 impl Rect {
-    pub fn new(x: i32, y: i32, w: i32, h: i32, filled: bool, color: Color) -> Rect {
-        Rect {x ,y, w, h, filled, color}
+    pub fn new(x: i32, y: i32, w: i32, h: i32, filled: bool, paint: impl Into<Paint>) -> Rect {
+        Rect {x ,y, w, h, filled, paint: paint.into(), radius: 0, border: 1}
+    }
+
+    pub fn with_radius(mut self, radius: i32) -> Rect {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_border(mut self, border: i32) -> Rect {
+        self.border = border;
+        self
+    }
+
+    // rounded corners and thick outlines are drawn one row span at a time
+    fn draw_spans(&self, fb: &mut FB) {
+        let area = (self.x, self.y, self.w, self.h);
+        let radius = self.radius.max(0).min(self.w / 2).min(self.h / 2);
+        let border = self.border.max(1);
+        let inner_radius = (radius - border).max(0);
+        for row in 0..self.h {
+            let outer = corner_inset(row, self.h, radius);
+            let (start, end) = (outer, self.w - outer);
+            let inner_row = row - border;
+            let inner_h = self.h - 2 * border;
+            if self.filled || inner_h <= 0 || inner_row < 0 || inner_row >= inner_h || self.w - 2 * border <= 0 {
+                self.draw_span(fb, start, end, row, area);
+            } else {
+                let inner = border + corner_inset(inner_row, inner_h, inner_radius);
+                self.draw_span(fb, start, inner.max(start), row, area);
+                self.draw_span(fb, (self.w - inner).min(end), end, row, area);
+            }
+        }
+    }
+
+    fn draw_span(&self, fb: &mut FB, start: i32, end: i32, row: i32, area: (i32, i32, i32, i32)) {
+        let y = self.y + row;
+        let x1 = (self.x + start).max(0);
+        let x2 = (self.x + end).min(fb.w as i32);
+        if y < 0 || y >= fb.h as i32 || x2 <= x1 {
+            return;
+        }
+        fb.fill_rect(x1 as u32, y as u32, (x2 - x1) as u32, 1, &self.paint, area);
     }
 
     fn clipped(&self, fb: &FB) -> Option<(u32, u32, u32, u32)>{
@@ -205,37 +328,46 @@ impl Rect {
 
 impl Draw for Rect {
     fn draw(&self, fb: &mut FB){
+        if self.radius > 0 || self.border > 1 {
+            self.draw_spans(fb);
+            return;
+        }
         //clip actual coordinates to render what is on screen or do nothing
+        // gradients are laid out over the whole rect, not just the visible part
+        let area = (self.x, self.y, self.w, self.h);
         match self.clipped(fb) {
             Some((x, y, w, h)) => {
                 if self.filled {
-                    fb.draw_filled_rect(x, y, w,h, &self.color );
+                    fb.fill_rect(x, y, w, h, &self.paint, area);
                 } else {
                     //only if x y w and h can we draw_rect ... otherwise we must do lines
                     if self.x as u32 == x && self.y as u32 == y && self.w as u32 == w && self.h as u32 == h { 
-                        fb.draw_rect(x, y, w, h, &self.color);
+                        fb.fill_rect(x, y, w, 1, &self.paint, area);
+                        fb.fill_rect(x, y + h - 1, w, 1, &self.paint, area);
+                        fb.fill_rect(x, y, 1, h, &self.paint, area);
+                        fb.fill_rect(x + w - 1, y, 1, h, &self.paint, area);
                         return ()
                     }
                     // check left
                     if self.x >= 0 && self.x < fb.w as i32 {
                         //render left
-                        fb.draw_v_line(x, y, h, &self.color)
+                        fb.fill_rect(x, y, 1, h, &self.paint, area)
                     }
                     // check top
                     if self.y >= 0 && self.y < fb.h as i32 {
                         // render top
-                        fb.draw_h_line(x, y, w, &self.color)
+                        fb.fill_rect(x, y, w, 1, &self.paint, area)
                     }
                     // check right
                     if self.x + self.w < fb.w as i32 && self.x + self.h >= 0 {
                         //render right
-                        fb.draw_v_line(x + w - 1, y, h, &self.color)
+                        fb.fill_rect(x + w - 1, y, 1, h, &self.paint, area)
 
                     }
                     // check bottom
                     if self.y + self.h < fb.h as i32 && self.y + self.h >= 0 {
                         //render bottom
-                        fb.draw_h_line(x, y + h -1, w, &self.color)
+                        fb.fill_rect(x, y + h - 1, w, 1, &self.paint, area)
                     }
 
                 }
@@ -256,9 +388,174 @@ impl Draw for Rect {
     }
 }
 
+// lines
+// connected line segments, a single line is a polyline with two points
+#[derive(Clone, Debug)]
+pub struct Polyline {
+    pub points: Vec<(i32, i32)>,
+    pub color: Color,
+    pub thickness: i32
+}
+
+impl Polyline {
+    pub fn new(points: Vec<(i32, i32)>, color: Color) -> Polyline {
+        Polyline {points, color, thickness: 1}
+    }
+
+    pub fn line(x1: i32, y1: i32, x2: i32, y2: i32, color: Color) -> Polyline {
+        Polyline::new(vec![(x1, y1), (x2, y2)], color)
+    }
+
+    pub fn with_thickness(mut self, thickness: i32) -> Polyline {
+        self.thickness = thickness.max(1);
+        self
+    }
+
+    // bounding box of all the points
+    fn bounds(&self) -> (i32, i32, i32, i32) {
+        let min_x = self.points.iter().map(|p| p.0).min().unwrap_or(0);
+        let min_y = self.points.iter().map(|p| p.1).min().unwrap_or(0);
+        let max_x = self.points.iter().map(|p| p.0).max().unwrap_or(0);
+        let max_y = self.points.iter().map(|p| p.1).max().unwrap_or(0);
+        let pad = self.thickness / 2;
+        (min_x - pad, min_y - pad, max_x - min_x + self.thickness, max_y - min_y + self.thickness)
+    }
+}
+
+// bresenham, pixels off the framebuffer are skipped
+pub fn draw_line(fb: &mut FB, from: (i32, i32), to: (i32, i32), thickness: i32, color: &Color) {
+    let (mut x, mut y) = from;
+    let dx = (to.0 - x).abs();
+    let dy = -(to.1 - y).abs();
+    let sx = if x < to.0 { 1 } else { -1 };
+    let sy = if y < to.1 { 1 } else { -1 };
+    let mut err = dx + dy;
+    let offset = thickness / 2;
+    loop {
+        for ty in 0..thickness {
+            for tx in 0..thickness {
+                let px = x + tx - offset;
+                let py = y + ty - offset;
+                if px >= 0 && py >= 0 {
+                    fb.put_pixel(px as u32, py as u32, color);
+                }
+            }
+        }
+        if x == to.0 && y == to.1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+impl Draw for Polyline {
+    fn draw(&self, fb: &mut FB) {
+        if self.clipped(fb).is_none() {
+            return;
+        }
+        match self.points.len() {
+            0 => (),
+            1 => draw_line(fb, self.points[0], self.points[0], self.thickness, &self.color),
+            _ => {
+                for segment in self.points.windows(2) {
+                    draw_line(fb, segment[0], segment[1], self.thickness, &self.color);
+                }
+            }
+        }
+    }
+    fn slide(&mut self, x: i32, y: i32) {
+        for point in self.points.iter_mut() {
+            point.0 += x;
+            point.1 += y;
+        }
+    }
+    fn clipped(&self, fb: &FB) -> Option<(u32, u32, u32, u32)>{
+        let (x, y, w, h) = self.bounds();
+        clipper(x, y, w, h, fb.w, fb.h)
+    }
+}
+
+// arcs
+// a band of a circle between radius - thickness and radius
+// angles are in degrees clockwise from 12 o'clock, sweep 360 is a full ring
+#[derive(Clone, Debug)]
+pub struct CircularArc {
+    pub cx: i32,
+    pub cy: i32,
+    pub radius: i32,
+    pub thickness: i32,
+    pub start: f32,
+    pub sweep: f32,
+    pub color: Color
+}
+
+impl CircularArc {
+    pub fn new(cx: i32, cy: i32, radius: i32, start: f32, sweep: f32, color: Color) -> CircularArc {
+        CircularArc {cx, cy, radius, thickness: 1, start, sweep, color}
+    }
+
+    pub fn with_thickness(mut self, thickness: i32) -> CircularArc {
+        self.thickness = thickness.max(1);
+        self
+    }
+
+    fn contains_angle(&self, angle: f32) -> bool {
+        if self.sweep >= 360.0 {
+            return true;
+        }
+        (angle - self.start).rem_euclid(360.0) <= self.sweep
+    }
+}
+
+// the point radius away from (cx, cy) at a clockwise from 12 o'clock angle
+pub fn point_on_circle(cx: i32, cy: i32, radius: f32, angle: f32) -> (i32, i32) {
+    let radians = angle.to_radians();
+    (cx + (radius * radians.sin()).round() as i32, cy - (radius * radians.cos()).round() as i32)
+}
+
+impl Draw for CircularArc {
+    fn draw(&self, fb: &mut FB) {
+        let (x, y, w, h) = match self.clipped(fb) {
+            Some(area) => area,
+            None => return
+        };
+        let outer = self.radius as f32 + 0.5;
+        let inner = (self.radius - self.thickness) as f32 + 0.5;
+        for py in y..y + h {
+            for px in x..x + w {
+                let dx = px as f32 - self.cx as f32;
+                let dy = py as f32 - self.cy as f32;
+                let distance = (dx * dx + dy * dy).sqrt();
+                if distance > outer || distance < inner {
+                    continue;
+                }
+                if self.contains_angle(dx.atan2(-dy).to_degrees()) {
+                    fb.put_pixel(px, py, &self.color);
+                }
+            }
+        }
+    }
+    fn slide(&mut self, x: i32, y: i32) {
+        self.cx += x;
+        self.cy += y;
+    }
+    fn clipped(&self, fb: &FB) -> Option<(u32, u32, u32, u32)>{
+        clipper(self.cx - self.radius, self.cy - self.radius, self.radius * 2 + 1, self.radius * 2 + 1, fb.w, fb.h)
+    }
+}
+
 // images
+// the decoded image is shared so several layers can show the same file
 pub struct Image {
-    img: DynamicImage,
+    img: Arc<DynamicImage>,
     // where it goes on the canvas
     x: i32,
     y: i32,
@@ -274,6 +571,12 @@ impl Image {
     #[allow(dead_code)] 
     pub fn new(path: &'static str, x: i32, y: i32, w: i32, h: i32, img_x: u32, img_y: u32 ) -> Image {
         let img =image::open(path).unwrap();
+        Image {
+            img: Arc::new(img), x, y, w, h, img_x, img_y
+        }
+    }
+
+    pub fn from_shared(img: Arc<DynamicImage>, x: i32, y: i32, w: i32, h: i32, img_x: u32, img_y: u32 ) -> Image {
         Image {
             img, x, y, w, h, img_x, img_y
         }
@@ -285,7 +588,11 @@ impl Draw for Image {
     fn draw(&self, fb: &mut FB){
         match self.clipped(fb) {
             Some((x, y, w, h)) => {
-                    fb.render_image(&self.img, x, y, w, h, self.img_x, self.img_y)
+                    // partly off the top / left, e.g. scrolled in a viewport
+                    fb.render_image(&self.img, x, y, w, h,
+                        adjust_img_loc(self.x, self.img_x, self.img.width()),
+                        adjust_img_loc(self.y, self.img_y, self.img.height())
+                    )
                 },
             None => ()
         }
@@ -446,6 +753,23 @@ impl Text {
 
 
 
+// how far a row of a rounded rect is pulled in from each side
+fn corner_inset(row: i32, h: i32, radius: i32) -> i32 {
+    if radius <= 0 {
+        return 0;
+    }
+    let from_edge = if row < radius {
+        row
+    } else if row >= h - radius {
+        h - 1 - row
+    } else {
+        return 0;
+    };
+    let dy = (radius - from_edge) as f32 - 0.5;
+    let r = radius as f32;
+    (r - (r * r - dy * dy).max(0.0).sqrt()).round() as i32
+}
+
 // generic clipper to be called by draw's clipped
 fn clipper(ix: i32, iy: i32, iw: i32, ih: i32, fw: u32, fh: u32) -> Option<(u32, u32, u32, u32)>{
         let x: i32;
@@ -0,0 +1,382 @@
+use super::Color;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor, SeqAccess};
+
+// Parsing, colour math and serde support for Color
+// so palettes can live in theme and config files
+
+#[derive(Debug, Clone)]
+pub struct ColorParseError {
+    pub input: String
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to parse color: {}", self.input)
+    }
+}
+
+impl Error for ColorParseError {}
+
+// css level 1 names plus a few common extras
+const NAMED_COLORS: [(&str, u8, u8, u8); 24] = [
+    ("black", 0, 0, 0),
+    ("silver", 192, 192, 192),
+    ("gray", 128, 128, 128),
+    ("grey", 128, 128, 128),
+    ("white", 255, 255, 255),
+    ("maroon", 128, 0, 0),
+    ("red", 255, 0, 0),
+    ("purple", 128, 0, 128),
+    ("fuchsia", 255, 0, 255),
+    ("magenta", 255, 0, 255),
+    ("green", 0, 128, 0),
+    ("lime", 0, 255, 0),
+    ("olive", 128, 128, 0),
+    ("yellow", 255, 255, 0),
+    ("navy", 0, 0, 128),
+    ("blue", 0, 0, 255),
+    ("teal", 0, 128, 128),
+    ("aqua", 0, 255, 255),
+    ("cyan", 0, 255, 255),
+    ("orange", 255, 165, 0),
+    ("pink", 255, 192, 203),
+    ("brown", 165, 42, 42),
+    ("gold", 255, 215, 0),
+    ("darkgray", 169, 169, 169),
+];
+
+impl Color {
+    // accepts #rgb, #rrggbb, #rrggbbaa, rgb(), rgba(), hsl(), hsla() and named colors
+    pub fn parse(input: &str) -> Result<Color, ColorParseError> {
+        let error = || ColorParseError {input: input.to_string()};
+        let value = input.trim().to_lowercase();
+
+        if let Some(hex) = value.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(error);
+        }
+        if let Some((function, args)) = split_function(&value) {
+            let args: Vec<&str> = args.split(',').map(|arg| arg.trim()).collect();
+            let color = match (function, args.len()) {
+                ("rgb", 3) | ("rgba", 4) => parse_rgb(&args),
+                ("hsl", 3) | ("hsla", 4) => parse_hsl(&args),
+                _ => None
+            };
+            return color.ok_or_else(error);
+        }
+        if value == "transparent" {
+            return Ok(Color::new_rgba(0, 0, 0, 0));
+        }
+        NAMED_COLORS.iter()
+            .find(|(name, _, _, _)| *name == value)
+            .map(|(_, r, g, b)| Color::new(*r, *g, *b))
+            .ok_or_else(error)
+    }
+
+    pub fn to_hex(&self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    // h in degrees, s and l from 0.0 to 1.0
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Color {
+        Color::from_hsla(h, s, l, 255)
+    }
+
+    pub fn from_hsla(h: f32, s: f32, l: f32, a: u8) -> Color {
+        let h = h.rem_euclid(360.0) / 360.0;
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        if s == 0.0 {
+            let v = to_u8(l);
+            return Color::new_rgba(v, v, v, a);
+        }
+        let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+        let p = 2.0 * l - q;
+        Color::new_rgba(
+            to_u8(hue_to_rgb(p, q, h + 1.0 / 3.0)),
+            to_u8(hue_to_rgb(p, q, h)),
+            to_u8(hue_to_rgb(p, q, h - 1.0 / 3.0)),
+            a
+        )
+    }
+
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let r = self.r as f32 / 255.0;
+        let g = self.g as f32 / 255.0;
+        let b = self.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        if max == min {
+            return (0.0, 0.0, l);
+        }
+        let d = max - min;
+        let s = if l > 0.5 { d / (2.0 - max - min) } else { d / (max + min) };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    pub fn with_alpha(&self, a: u8) -> Color {
+        Color::new_rgba(self.r, self.g, self.b, a)
+    }
+
+    // raise lightness by amount (0.0 - 1.0)
+    pub fn lighten(&self, amount: f32) -> Color {
+        let (h, s, l) = self.to_hsl();
+        Color::from_hsla(h, s, l + amount, self.a)
+    }
+
+    // lower lightness by amount (0.0 - 1.0)
+    pub fn darken(&self, amount: f32) -> Color {
+        self.lighten(-amount)
+    }
+
+    // weight 0.0 is all self, 1.0 is all other
+    pub fn mix(&self, other: &Color, weight: f32) -> Color {
+        let weight = weight.clamp(0.0, 1.0);
+        let mix_c = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * weight).round() as u8;
+        Color::new_rgba(
+            mix_c(self.r, other.r),
+            mix_c(self.g, other.g),
+            mix_c(self.b, other.b),
+            mix_c(self.a, other.a)
+        )
+    }
+
+    // WCAG relative luminance
+    pub fn luminance(&self) -> f32 {
+        let channel = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        };
+        0.2126 * channel(self.r) + 0.7152 * channel(self.g) + 0.0722 * channel(self.b)
+    }
+
+    // WCAG contrast ratio, 1.0 (none) to 21.0 (black on white)
+    pub fn contrast_ratio(&self, other: &Color) -> f32 {
+        let l1 = self.luminance();
+        let l2 = other.luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+
+    // black or white, whichever reads better on top of self
+    pub fn contrasting_text(&self) -> Color {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+        if self.contrast_ratio(&black) >= self.contrast_ratio(&white) {
+            black
+        } else {
+            white
+        }
+    }
+}
+
+impl PartialEq for Color {
+    fn eq(&self, other: &Color) -> bool {
+        self.r == other.r && self.g == other.g && self.b == other.b && self.a == other.a
+    }
+}
+
+impl Eq for Color {}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Color, ColorParseError> {
+        Color::parse(s)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+// colors are written out as hex strings
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+// colors are read from any string Color::parse understands or an [r, g, b(, a)] array
+// binary formats like bincode cannot deserialize_any, so they only get the string
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Color, D::Error> where D: Deserializer<'de> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(ColorVisitor)
+        } else {
+            deserializer.deserialize_str(ColorVisitor)
+        }
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a color string or an [r, g, b, a] array")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Color, E> where E: de::Error {
+        Color::parse(value).map_err(E::custom)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Color, A::Error> where A: SeqAccess<'de> {
+        let mut channels: Vec<u8> = vec![];
+        while let Some(channel) = seq.next_element::<u8>()? {
+            channels.push(channel);
+        }
+        match channels[..] {
+            [r, g, b] => Ok(Color::new(r, g, b)),
+            [r, g, b, a] => Ok(Color::new_rgba(r, g, b, a)),
+            _ => Err(de::Error::invalid_length(channels.len(), &self))
+        }
+    }
+}
+
+// helpers
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let nibble = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|n| n * 17);
+    match hex.len() {
+        3 => Some(Color::new(nibble(0)?, nibble(1)?, nibble(2)?)),
+        6 => Some(Color::new(byte(0)?, byte(2)?, byte(4)?)),
+        8 => Some(Color::new_rgba(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+        _ => None
+    }
+}
+
+// "rgb(1, 2, 3)" -> ("rgb", "1, 2, 3")
+fn split_function(value: &str) -> Option<(&str, &str)> {
+    let open = value.find('(')?;
+    let args = value[open + 1..].strip_suffix(')')?;
+    Some((value[..open].trim(), args))
+}
+
+// channels are 0 - 255 or a percentage
+fn parse_channel(arg: &str) -> Option<u8> {
+    match arg.strip_suffix('%') {
+        Some(percent) => Some(to_u8(percent.trim().parse::<f32>().ok()? / 100.0)),
+        None => Some(arg.parse::<f32>().ok()?.round().clamp(0.0, 255.0) as u8)
+    }
+}
+
+// alpha is 0.0 - 1.0 or a percentage
+fn parse_alpha(arg: Option<&&str>) -> Option<u8> {
+    match arg {
+        Some(arg) => match arg.strip_suffix('%') {
+            Some(percent) => Some(to_u8(percent.trim().parse::<f32>().ok()? / 100.0)),
+            None => Some(to_u8(arg.parse::<f32>().ok()?))
+        },
+        None => Some(255)
+    }
+}
+
+fn parse_percent(arg: &str) -> Option<f32> {
+    Some(arg.strip_suffix('%')?.trim().parse::<f32>().ok()? / 100.0)
+}
+
+fn parse_rgb(args: &[&str]) -> Option<Color> {
+    Some(Color::new_rgba(
+        parse_channel(args[0])?,
+        parse_channel(args[1])?,
+        parse_channel(args[2])?,
+        parse_alpha(args.get(3))?
+    ))
+}
+
+fn parse_hsl(args: &[&str]) -> Option<Color> {
+    let h = args[0].trim_end_matches("deg").parse::<f32>().ok()?;
+    Some(Color::from_hsla(h, parse_percent(args[1])?, parse_percent(args[2])?, parse_alpha(args.get(3))?))
+}
+
+fn hue_to_rgb(p: f32, q: f32, t: f32) -> f32 {
+    let t = t.rem_euclid(1.0);
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
+fn to_u8(v: f32) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_forms() {
+        assert_eq!(Color::parse("#fff").unwrap(), Color::new(255, 255, 255));
+        assert_eq!(Color::parse("#1a2B3c").unwrap(), Color::new(0x1a, 0x2b, 0x3c));
+        assert_eq!(Color::parse(" #10203040 ").unwrap(), Color::new_rgba(0x10, 0x20, 0x30, 0x40));
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        assert!(Color::parse("#ff").is_err());
+        assert!(Color::parse("#gggggg").is_err());
+        assert!(Color::parse("#ffé").is_err());
+    }
+
+    #[test]
+    fn parses_functions_and_names() {
+        assert_eq!(Color::parse("rgb(255, 0, 0)").unwrap(), Color::new(255, 0, 0));
+        assert_eq!(Color::parse("rgba(0, 0, 255, 0.5)").unwrap(), Color::new_rgba(0, 0, 255, 128));
+        assert_eq!(Color::parse("rgb(100%, 0%, 300)").unwrap(), Color::new(255, 0, 255));
+        assert_eq!(Color::parse("hsl(120deg, 100%, 50%)").unwrap(), Color::new(0, 255, 0));
+        assert_eq!(Color::parse("Navy").unwrap(), Color::new(0, 0, 128));
+        assert_eq!(Color::parse("transparent").unwrap().a, 0);
+    }
+
+    #[test]
+    fn rejects_wrong_argument_counts_and_unknown_names() {
+        assert!(Color::parse("rgb(1, 2)").is_err());
+        assert!(Color::parse("rgba(1, 2, 3)").is_err());
+        assert!(Color::parse("hsl(1, 2, 3)").is_err());
+        assert!(Color::parse("chartreuse-ish").is_err());
+        assert!(Color::parse("").is_err());
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        for hex in ["#000000", "#1a2b3c", "#ffffff80"].iter() {
+            assert_eq!(Color::parse(hex).unwrap().to_hex(), *hex);
+        }
+    }
+
+    #[test]
+    fn contrasting_text_is_black_or_white() {
+        assert_eq!(Color::new(255, 255, 255).contrasting_text(), Color::new(0, 0, 0));
+        assert_eq!(Color::new(0, 0, 0).contrasting_text(), Color::new(255, 255, 255));
+        assert_eq!(Color::new(0xf9, 0xa8, 0x25).contrasting_text(), Color::new(0, 0, 0));
+        assert_eq!(Color::new(0, 0, 128).contrasting_text(), Color::new(255, 255, 255));
+    }
+}
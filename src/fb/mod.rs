@@ -1,5 +1,9 @@
 use framebuffer::{Framebuffer};
 use image::{GenericImageView,  DynamicImage};
+use serde::{Serialize, Deserialize};
+
+mod color;
+pub use color::*;
 
 #[derive(Clone, Debug)]
 pub struct Color {
@@ -53,6 +57,179 @@ impl Color {
 
 }
 
+// Paint
+// what an area gets filled with, either a flat colour or a gradient
+// gradient coordinates are fractions of the area being painted
+// so a gradient follows its Rect when it slides around the canvas
+// in theme files a paint is either a color string or a gradient table
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Paint {
+    Solid(Color),
+    Linear(LinearGradient),
+    Radial(RadialGradient)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: Color
+}
+
+impl ColorStop {
+    pub fn new(offset: f32, color: Color) -> ColorStop {
+        ColorStop {offset, color}
+    }
+}
+
+// runs from (x1, y1) to (x2, y2)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinearGradient {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub stops: Vec<ColorStop>
+}
+
+impl LinearGradient {
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32, stops: Vec<ColorStop>) -> LinearGradient {
+        LinearGradient {x1, y1, x2, y2, stops}
+    }
+    pub fn vertical(top: Color, bottom: Color) -> LinearGradient {
+        LinearGradient::new(0.0, 0.0, 0.0, 1.0, vec![ColorStop::new(0.0, top), ColorStop::new(1.0, bottom)])
+    }
+    pub fn horizontal(left: Color, right: Color) -> LinearGradient {
+        LinearGradient::new(0.0, 0.0, 1.0, 0.0, vec![ColorStop::new(0.0, left), ColorStop::new(1.0, right)])
+    }
+}
+
+// centered on (cx, cy), radius is a fraction of the longest side of the area
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RadialGradient {
+    pub cx: f32,
+    pub cy: f32,
+    pub radius: f32,
+    pub stops: Vec<ColorStop>
+}
+
+impl RadialGradient {
+    pub fn new(cx: f32, cy: f32, radius: f32, stops: Vec<ColorStop>) -> RadialGradient {
+        RadialGradient {cx, cy, radius, stops}
+    }
+    pub fn centered(inner: Color, outer: Color) -> RadialGradient {
+        RadialGradient::new(0.5, 0.5, 0.5, vec![ColorStop::new(0.0, inner), ColorStop::new(1.0, outer)])
+    }
+}
+
+impl Paint {
+    // the color at (x, y) inside an area of w x h
+    pub fn color_at(&self, x: i32, y: i32, w: i32, h: i32) -> Color {
+        match self {
+            Paint::Solid(color) => color.clone(),
+            Paint::Linear(gradient) => {
+                let w = w.max(1) as f32;
+                let h = h.max(1) as f32;
+                // project the point onto the gradient line
+                let dx = (gradient.x2 - gradient.x1) * w;
+                let dy = (gradient.y2 - gradient.y1) * h;
+                let length = dx * dx + dy * dy;
+                if length == 0.0 {
+                    return sample_stops(&gradient.stops, 0.0);
+                }
+                let px = x as f32 - gradient.x1 * w;
+                let py = y as f32 - gradient.y1 * h;
+                sample_stops(&gradient.stops, (px * dx + py * dy) / length)
+            },
+            Paint::Radial(gradient) => {
+                let radius = gradient.radius * w.max(h) as f32;
+                if radius <= 0.0 {
+                    return sample_stops(&gradient.stops, 1.0);
+                }
+                let px = x as f32 - gradient.cx * w as f32;
+                let py = y as f32 - gradient.cy * h as f32;
+                sample_stops(&gradient.stops, (px * px + py * py).sqrt() / radius)
+            }
+        }
+    }
+
+    // representative flat color, used where a gradient cannot be drawn
+    pub fn base_color(&self) -> Color {
+        match self {
+            Paint::Solid(color) => color.clone(),
+            Paint::Linear(gradient) => sample_stops(&gradient.stops, 0.0),
+            Paint::Radial(gradient) => sample_stops(&gradient.stops, 0.0)
+        }
+    }
+
+    // color does not change along a row of pixels
+    fn is_row_constant(&self) -> bool {
+        match self {
+            Paint::Solid(_) => true,
+            Paint::Linear(gradient) => gradient.x1 == gradient.x2,
+            Paint::Radial(_) => false
+        }
+    }
+
+    // same paint with the alpha channel removed
+    pub fn opaque(&self) -> Paint {
+        let opaque_stops = |stops: &Vec<ColorStop>| -> Vec<ColorStop> {
+            stops.iter().map(|stop| ColorStop::new(stop.offset, Color::new(stop.color.r, stop.color.g, stop.color.b))).collect()
+        };
+        match self {
+            Paint::Solid(color) => Paint::Solid(Color::new(color.r, color.g, color.b)),
+            Paint::Linear(gradient) => Paint::Linear(LinearGradient {stops: opaque_stops(&gradient.stops), ..gradient.clone()}),
+            Paint::Radial(gradient) => Paint::Radial(RadialGradient {stops: opaque_stops(&gradient.stops), ..gradient.clone()})
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Paint {
+        Paint::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Paint {
+    fn from(gradient: LinearGradient) -> Paint {
+        Paint::Linear(gradient)
+    }
+}
+
+impl From<RadialGradient> for Paint {
+    fn from(gradient: RadialGradient) -> Paint {
+        Paint::Radial(gradient)
+    }
+}
+
+fn sample_stops(stops: &[ColorStop], t: f32) -> Color {
+    let first = match stops.first() {
+        Some(stop) => stop,
+        None => return Color::new(0, 0, 0)
+    };
+    if t <= first.offset {
+        return first.color.clone();
+    }
+    for pair in stops.windows(2) {
+        let (from, to) = (&pair[0], &pair[1]);
+        if t <= to.offset {
+            let span = to.offset - from.offset;
+            let p = if span > 0.0 { (t - from.offset) / span } else { 1.0 };
+            return Color::new_rgba(
+                lerp_c(from.color.r, to.color.r, p),
+                lerp_c(from.color.g, to.color.g, p),
+                lerp_c(from.color.b, to.color.b, p),
+                lerp_c(from.color.a, to.color.a, p)
+            );
+        }
+    }
+    stops[stops.len() - 1].color.clone()
+}
+
+fn lerp_c(from: u8, to: u8, p: f32) -> u8 {
+    (from as f32 + (to as f32 - from as f32) * p).round() as u8
+}
+
 // helper functions
 fn or_255(sp: u16) -> u8 {
     if sp > 255 {
@@ -101,7 +278,9 @@ pub struct FB {
     ll: u32,
     bpp: u32,
     frame: Vec<u8>,
-    background: Color
+    background: Paint,
+    // x, y, w, h that drawing is limited to, see set_clip
+    clip: Option<(u32, u32, u32, u32)>
 }
 
 impl FB {
@@ -122,7 +301,8 @@ impl FB {
             ll: line_length,
             bpp: bytespp,
             frame: frame,
-            background: Color::new(0,0,0)
+            background: Paint::Solid(Color::new(0,0,0)),
+            clip: None
         } 
     }
 
@@ -135,8 +315,41 @@ impl FB {
     }
 
     #[allow(dead_code)]
-    pub fn set_background(&mut self, color: Color){
-        self.background = color
+    pub fn set_background(&mut self, background: impl Into<Paint>){
+        self.background = background.into()
+    }
+
+    // limit drawing to a rectangle of the screen, None draws everywhere again
+    pub fn set_clip(&mut self, clip: Option<(u32, u32, u32, u32)>) {
+        self.clip = clip.map(|(x, y, w, h)| {
+            let x = x.min(self.w);
+            let y = y.min(self.h);
+            (x, y, w.min(self.w - x), h.min(self.h - y))
+        });
+    }
+
+    fn in_clip(&self, x: u32, y: u32) -> bool {
+        match self.clip {
+            Some((cx, cy, cw, ch)) => x >= cx && x < cx + cw && y >= cy && y < cy + ch,
+            None => true
+        }
+    }
+
+    // the part of a line inside the clip, as (start, len) along the line
+    // along and across are the clip's (start, len) in the line's direction and across it
+    fn clip_run(start: u32, len: u32, cross: u32, along: (u32, u32), across: (u32, u32)) -> Option<(u32, u32)> {
+        let (clip_start, clip_len) = along;
+        let (cross_start, cross_len) = across;
+        if cross < cross_start || cross >= cross_start + cross_len {
+            return None;
+        }
+        let from = start.max(clip_start);
+        let to = (start + len).min(clip_start + clip_len);
+        if to > from {
+            Some((from, to - from))
+        } else {
+            None
+        }
     }
 
     fn check_x(&self, x: u32) -> u32 {
@@ -168,7 +381,47 @@ impl FB {
     }
 
     pub fn clear(&mut self) {
-        self.draw_filled_rect(0, 0, 240, 240, &Color::new(self.background.r, self.background.g, self.background.b));
+        let background = self.background.opaque();
+        let (w, h) = (self.w, self.h);
+        self.fill_rect(0, 0, w, h, &background, (0, 0, w as i32, h as i32));
+    }
+
+    // fill x, y, w, h with paint
+    // area is the full, unclipped, geometry the paint is laid out over
+    pub fn fill_rect(&mut self, x1: u32, y1: u32, width: u32, height: u32, paint: &Paint, area: (i32, i32, i32, i32)) {
+        let (area_x, area_y, area_w, area_h) = area;
+        if let Paint::Solid(color) = paint {
+            self.draw_filled_rect(x1, y1, width, height, color);
+        } else if paint.is_row_constant() {
+            for i in 0..height {
+                let y = y1 + i;
+                let color = paint.color_at(0, y as i32 - area_y, area_w, area_h);
+                self.draw_h_line(x1, y, width, &color);
+            }
+        } else {
+            for i in 0..height {
+                for j in 0..width {
+                    let (x, y) = (x1 + j, y1 + i);
+                    let color = paint.color_at(x as i32 - area_x, y as i32 - area_y, area_w, area_h);
+                    self.put_pixel(x, y, &color);
+                }
+            }
+        }
+    }
+
+    pub fn put_pixel(&mut self, x: u32, y: u32, color: &Color) {
+        if x >= self.w || y >= self.h || !self.in_clip(x, y) {
+            return;
+        }
+        let index = self.find_point(x, y);
+        let rgb565 = if color.a == 255 {
+            color.to_16b()
+        } else {
+            let base_color = self.frame[index] as u16 + ((self.frame[index + 1] as u16) << 8);
+            Color::from_16b(base_color).add(color).to_16b()
+        };
+        self.frame[index] = rgb565 as u8;
+        self.frame[index + 1] = (rgb565 >> 8) as u8;
     }
 
     pub fn draw_rect(&mut self, x1: u32, y1: u32, width: u32,  height: u32, color: &Color) {
@@ -185,6 +438,13 @@ impl FB {
     }
 
     pub fn draw_h_line(&mut self, x1: u32, y1: u32, width: u32, color: &Color){
+        let (x1, width) = match self.clip {
+            Some((cx, cy, cw, ch)) => match FB::clip_run(x1, width, y1, (cx, cw), (cy, ch)) {
+                Some(run) => run,
+                None => return
+            },
+            None => (x1, width)
+        };
         let x = self.check_x(x1);
         let y = self.check_y(y1);
         let w = self.check_w(x, width);
@@ -211,6 +471,13 @@ impl FB {
     }
 
     pub fn draw_v_line(&mut self, x1: u32, y1: u32, height: u32, color: &Color) {
+        let (y1, height) = match self.clip {
+            Some((cx, cy, cw, ch)) => match FB::clip_run(y1, height, x1, (cy, ch), (cx, cw)) {
+                Some(run) => run,
+                None => return
+            },
+            None => (y1, height)
+        };
         let x = self.check_x(x1);
         let y = self.check_y(y1);
         let h = self.check_h(y, height);
@@ -246,6 +513,9 @@ impl FB {
         }
         for x in 0..(w - 1) {
             for y in 0..(h - 1) {
+                if !self.in_clip(x + start_x, y + start_y) {
+                    continue;
+                }
                 let px = img.get_pixel(img_x + x, img_y + y);
                 let index = self.find_point(x + start_x, y + start_y );
                 let color = Color::new_rgba(px[0], px[1], px[2], px[3]);
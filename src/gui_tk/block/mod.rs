@@ -5,9 +5,7 @@ use log::*;
 pub struct Block {
     pub event: Event,
     pub name: String,
-    pub regular_name: String,
-    pub selected_name: String,
-    pub clicked_name: String,
+    pub state_layers: StateLayers,
     // cloned and appended to canvas
     pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
     pub x: i32,
@@ -15,6 +13,7 @@ pub struct Block {
     pub w: i32,
     pub h: i32,
     pub gui_state: GuiState,
+    pub style: Style,
     config: &'static GuiConfig
 }
 
@@ -23,10 +22,7 @@ impl Block {
     pub fn new(x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Block {
         let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
         let name = format!("Block - {}", uuid_string); 
-
-        let regular_name = format!("{} - regular", name);
-        let selected_name = format!("{} - selected", name);
-        let clicked_name = format!("{} - clicked", name);
+        let state_layers = StateLayers::new(&name);
 
         let gui_state =  GuiState::Base;
 
@@ -35,25 +31,22 @@ impl Block {
         let mut block = Block {
             event,
             name,
-            regular_name,
-            clicked_name,
-            selected_name,
+            state_layers,
             layers,
             x,
             y,
             w,
             h,
             gui_state,
+            style: Style::new(),
             config
         };
         block.gen_layers();
         block
     }
     pub fn reinit(&mut self, canvas: &mut Canvas){
-        canvas.drop_layer_group(self.regular_name.clone());
-        canvas.drop_layer_group(self.selected_name.clone());
-        canvas.drop_layer_group(self.clicked_name.clone());
- 
+        self.state_layers.drop(canvas);
+
         //gen new layers
         self.gen_layers();
         self.initialize(canvas);
@@ -61,58 +54,52 @@ impl Block {
 
     }
 
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Block {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
     pub fn gen_layers(&mut self)  {
-        
-        // basic background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                        self.x,
-                        self.y, 
-                        self.w, 
-                        self.h, 
-                        true, 
-                        self.config.palette.base_background.clone()
-                )
-            ), 
-            false, 
-            self.regular_name.clone()
-        );
-        self.layers.push(bg);
-
-        // Clicked background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    true, 
-                    self.config.palette.clicked_background.clone()
-                )
-            ), 
-            false, 
-            self.clicked_name.clone()
-        );
-        self.layers.push(bg);
-
-        // Selected background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    true, 
-                    self.config.palette.selected_background.clone()
-                )
-            ), 
-            false, 
-            self.selected_name.clone()
-        );
-        self.layers.push(bg);
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            // background box
+            let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
+                Box::new(
+                    Rect::new(
+                            self.x,
+                            self.y, 
+                            self.w, 
+                            self.h, 
+                            true, 
+                            self.style.background_for(gui_state, self.config)
+                    ).with_radius(self.style.radius_for())
+                ), 
+                false, 
+                group.clone()
+            );
+            self.layers.push(bg);
+            // blocks only get an outline when the style asks for one, border 0 is none
+            if self.style.border.unwrap_or(0) > 0 {
+                let outline: Layer<Box<dyn Draw + Send>> = Layer::new(
+                    Box::new(
+                        Rect::new(
+                            self.x, 
+                            self.y, 
+                            self.w, 
+                            self.h, 
+                            false, 
+                            self.style.outline_for(gui_state, self.config)
+                        ).with_radius(self.style.radius_for()).with_border(self.style.border_for())
+                    ), 
+                    false, 
+                    group.clone()
+                );
+                self.layers.push(outline);
+            }
+        }
         debug!("Copied Layers to canvas for {}", self.name);
     }
 }
@@ -125,36 +112,38 @@ impl Gui for Block {
     }
     fn activate(&mut self, canvas: &mut Canvas) -> bool {
         //set the correct layers to active        
-        match self.gui_state {
-            GuiState::Base => {
-                canvas.activate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-                debug!("Activate Base: {}", self.name);
-            },
-            GuiState::Clicked => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.activate_layer_group(self.clicked_name.clone());
-                debug!("Activate Clicked: {}", self.name);
-            },
-            GuiState::Selected => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.activate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-                debug!("Activate Selected: {}", self.name);
-            }
-        };
+        self.state_layers.activate(&self.gui_state, canvas);
+        debug!("Activate {:?}: {}", self.gui_state, self.name);
         true
     }
 
     fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
-        canvas.deactivate_layer_group(self.regular_name.clone());
-        canvas.deactivate_layer_group(self.selected_name.clone());
-        canvas.deactivate_layer_group(self.clicked_name.clone());
+        self.state_layers.deactivate(canvas);
         true 
     }
 
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
     #[allow(unused_variables)]
     fn set_text(&mut self, text: String, canvas: &mut Canvas) {
     }
@@ -171,10 +160,10 @@ impl Gui for Block {
         self.gui_state.clone()
     }
 
-    fn handle_hid_event(&mut self, h_e: &HIDEvent) -> (bool, Option<GuiState>, Option<Event>) {
-        match h_e.code {
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match h_e.action {
             // handle hat press
-            6 => {
+            Action::Select => {
                 match h_e.io_state {
                     IOState::Pressed => {
                             (false, Some(GuiState::Clicked), Some(self.event.clone()))
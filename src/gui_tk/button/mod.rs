@@ -6,9 +6,7 @@ pub struct Button {
     pub text: String,
     pub event: Event,
     pub name: String,
-    pub regular_name: String,
-    pub selected_name: String,
-    pub clicked_name: String,
+    pub state_layers: StateLayers,
     // cloned and appended to canvas
     pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
     pub x: i32,
@@ -17,8 +15,10 @@ pub struct Button {
     pub h: i32,
     pub text_x: i32,
     pub text_y: i32,
+    // font size, 0 follows the config
     pub text_h: i32,
     pub gui_state: GuiState,
+    pub style: Style,
     config: &'static GuiConfig
 }
 
@@ -27,10 +27,7 @@ impl Button {
     pub fn new(text: String, x: i32, y: i32, w: i32, h: i32, text_x: i32, text_y: i32, text_h: i32, event: Event, config: &'static GuiConfig) -> Button {
         let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
         let name = format!("Button - {}", uuid_string); 
-
-        let regular_name = format!("{} - regular", name);
-        let selected_name = format!("{} - selected", name);
-        let clicked_name = format!("{} - clicked", name);
+        let state_layers = StateLayers::new(&name);
 
         let gui_state =  GuiState::Base;
 
@@ -40,9 +37,7 @@ impl Button {
             text,
             event,
             name,
-            regular_name,
-            clicked_name,
-            selected_name,
+            state_layers,
             layers,
             x,
             y,
@@ -52,16 +47,15 @@ impl Button {
             text_y,
             text_h,
             gui_state,
+            style: Style::new(),
             config
         };
         button.gen_layers();
         button
     }
     pub fn reinit(&mut self, canvas: &mut Canvas){
-        canvas.drop_layer_group(self.regular_name.clone());
-        canvas.drop_layer_group(self.selected_name.clone());
-        canvas.drop_layer_group(self.clicked_name.clone());
- 
+        self.state_layers.drop(canvas);
+
         //gen new layers
         self.gen_layers();
         self.initialize(canvas);
@@ -69,169 +63,53 @@ impl Button {
 
     }
 
-    pub fn gen_layers(&mut self)  {
-        
-        // basic background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x,
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    true, 
-                    self.config.palette.base_background.clone()
-                )
-            ), 
-            false, 
-            self.regular_name.clone()
-        );
-        let outline: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    false, 
-                    self.config.palette.base.clone()
-                )
-            ), 
-            false, 
-            self.regular_name.clone()
-        );
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x,
-                self.y + self.text_y, 
-                self.text_h as f32, 
-                self.text.clone(), 
-                &self.config.font,  
-                self.config.palette.base_text.clone(), 
-                2
-            )
-        );
-        let text_width = text.w;
-        if self.text_x > 0 {
-            text.x += self.text_x;
-        } else if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
-        }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(
-            text, 
-            false, 
-            self.regular_name.clone()
-        ); 
-
-        self.layers.push(bg);
-        self.layers.push(outline);
-        self.layers.push(text_layer);
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Button {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
 
-        // Clicked background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    true,
-                    self.config.palette.clicked_background.clone()
-                )
-            ), 
-            false, 
-            self.clicked_name.clone()
-        );
-        let outline: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    false, 
-                    self.config.palette.clicked.clone()
-                )
-            ), 
-            false, 
-            self.clicked_name.clone()
-        );
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x, 
-                self.y + self.text_y, 
-                self.text_h as f32, 
-                self.text.clone(), 
-                &self.config.font, 
-                self.config.palette.clicked_text.clone(), 
-            2)
-        );
-        let text_width = text.w;
-        if self.text_x > 0 {
-            text.x += self.text_x;
-        } else if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
+    // a text_h of 0 follows the config, so themes can resize the text
+    fn font_size(&self) -> f32 {
+        if self.text_h > 0 {
+            self.text_h as f32
+        } else {
+            self.config.font_size
         }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, self.clicked_name.clone()); 
-
-        self.layers.push(bg);
-        self.layers.push(outline);
-        self.layers.push(text_layer);
+    }
 
-        // Selected background box
-        let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    true, 
-                    self.config.palette.selected_background.clone()
-                )
-            ), 
-            false, 
-            self.selected_name.clone()
-        );
-        let outline: Layer<Box<dyn Draw + Send>> = Layer::new(
-            Box::new(
-                Rect::new(
-                    self.x, 
-                    self.y, 
-                    self.w, 
-                    self.h, 
-                    false, 
-                    self.config.palette.selected.clone()
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+            let mut text: Box<Text> = Box::new(
+                Text::new(
+                    self.x,
+                    self.y + self.text_y, 
+                    self.style.font_size_for(self.font_size()), 
+                    self.text.clone(), 
+                    &self.config.font,  
+                    self.style.text_for(gui_state, self.config), 
+                    self.style.padding_for(self.config)
                 )
-            ), 
-            false, 
-            self.selected_name.clone()
-        );
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x, 
-                self.y + self.text_y, 
-                self.text_h as f32, 
-                self.text.clone(), 
-                &self.config.font,  
-                self.config.palette.selected_text.clone(), 
-                2
-            )
-        );
-        let text_width = text.w;
-        if self.text_x > 0 {
-            text.x += self.text_x;
-        } else if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
-        }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, self.selected_name.clone()); 
+            );
+            let text_width = text.w;
+            if self.text_x > 0 {
+                text.x += self.text_x;
+            } else if text_width < self.w {
+                let x_offset = (self.w - text_width) / 2;
+                text.x = self.x + x_offset;
+            }
+            let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(
+                text, 
+                false, 
+                group.clone()
+            ); 
 
-        self.layers.push(bg);
-        self.layers.push(outline);
-        self.layers.push(text_layer);
+            self.layers.push(text_layer);
+        }
     }
 }
 impl Gui for Button {
@@ -243,39 +121,42 @@ impl Gui for Button {
     }
     fn activate(&mut self, canvas: &mut Canvas) -> bool {
         //set the correct layers to active        
-        match self.gui_state {
-            GuiState::Base => {
-                canvas.activate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-            },
-            GuiState::Clicked => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.activate_layer_group(self.clicked_name.clone());
-            },
-            GuiState::Selected => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.activate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-            }
-        };
+        self.state_layers.activate(&self.gui_state, canvas);
+        debug!("Activate {:?}: {}", self.gui_state, self.name);
         true
     }
 
     fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
-        canvas.deactivate_layer_group(self.regular_name.clone());
-        canvas.deactivate_layer_group(self.selected_name.clone());
-        canvas.deactivate_layer_group(self.clicked_name.clone());
+        self.state_layers.deactivate(canvas);
         true 
     }
 
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
     fn set_text(&mut self, text: String, canvas: &mut Canvas) {
         self.text = text;
-        // update regular_name
-        self.layers.append( &mut canvas.get_layer_group(self.regular_name.clone()));
-        self.layers.append( &mut canvas.get_layer_group(self.selected_name.clone()));
-        self.layers.append( &mut canvas.get_layer_group(self.clicked_name.clone()));
+        // update every state's text layer
+        self.layers.append(&mut self.state_layers.take(canvas));
 
         for layer in self.layers.iter_mut() {
             layer.item.update_text(self.text.clone())
@@ -295,9 +176,9 @@ impl Gui for Button {
         self.gui_state.clone()
     }
 
-    fn handle_hid_event(&mut self, h_e: &HIDEvent) -> (bool, Option<GuiState>, Option<Event>) {
-        match h_e.code {
-            6 => {
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match h_e.action {
+            Action::Select => {
                 match h_e.io_state {
                     IOState::Pressed => {
                             (false, Some(GuiState::Clicked), Some(self.event.clone()))
@@ -0,0 +1,399 @@
+use super::*;
+use std::collections::VecDeque;
+
+// Series
+// one line on a chart, a ring buffer of the latest samples
+pub struct Series {
+    pub name: &'static str,
+    pub color: Option<Color>,
+    pub samples: VecDeque<f64>
+}
+
+impl Series {
+    pub fn new(name: &'static str, capacity: usize) -> Series {
+        Series {
+            name,
+            color: None,
+            samples: VecDeque::with_capacity(capacity)
+        }
+    }
+}
+
+// Chart
+// read only line chart, or sparkline without grid and labels
+// new samples are pushed from the view updater with push_value / set_value,
+// only the lines are redrawn unless the y range changes
+pub struct Chart {
+    pub series: Vec<Series>,
+    pub capacity: usize,
+    // fixed y range, None scales to the samples
+    pub y_range: Option<(f64, f64)>,
+    pub grid_rows: usize,
+    pub grid_columns: usize,
+    pub labels: bool,
+    pub precision: usize,
+    pub unit: &'static str,
+    pub x_label: Option<String>,
+    pub name: String,
+    pub state_layers: StateLayers,
+    pub plot_name: String,
+    pub label_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub gui_state: GuiState,
+    pub style: Style,
+    range: (f64, f64),
+    dirty: bool,
+    labels_dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl Chart {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(series: Vec<&'static str>, capacity: usize, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> Chart {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let name = format!("Chart - {}", uuid_string);
+        let plot_name = format!("{} - plot", name);
+        let label_name = format!("{} - labels", name);
+        let state_layers = StateLayers::new(&name).with_live(&plot_name).with_live(&label_name);
+        let capacity = capacity.max(2);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut chart = Chart {
+            series: series.into_iter().map(|name| Series::new(name, capacity)).collect(),
+            capacity,
+            y_range: None,
+            grid_rows: 0,
+            grid_columns: 0,
+            labels: false,
+            precision: 0,
+            unit: "",
+            x_label: None,
+            name,
+            state_layers,
+            plot_name,
+            label_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            range: (0.0, 1.0),
+            dirty: false,
+            labels_dirty: false,
+            config
+        };
+        chart.gen_layers();
+        chart
+    }
+
+    // fix the y axis instead of scaling to the samples
+    pub fn with_y_range(mut self, min: f64, max: f64) -> Chart {
+        self.y_range = Some((min.min(max), max.max(min)));
+        self.range = self.compute_range();
+        self
+    }
+
+    // gridlines dividing the plot into rows and columns
+    pub fn with_grid(mut self, rows: usize, columns: usize) -> Chart {
+        self.grid_rows = rows;
+        self.grid_columns = columns;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    // min / mid / max y labels, e.g. (1, "°C")
+    pub fn with_labels(mut self, precision: usize, unit: &'static str) -> Chart {
+        self.labels = true;
+        self.precision = precision;
+        self.unit = unit;
+        self
+    }
+
+    // caption along the bottom edge, e.g. "last 10 min"
+    pub fn with_x_label(mut self, x_label: String) -> Chart {
+        self.x_label = Some(x_label);
+        self
+    }
+
+    pub fn with_series_color(mut self, series: usize, color: Color) -> Chart {
+        if let Some(series) = self.series.get_mut(series) {
+            series.color = Some(color);
+        }
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Chart {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    // append a sample, dropping the oldest once the buffer is full
+    pub fn push(&mut self, series: usize, value: f64) {
+        let capacity = self.capacity;
+        match self.series.get_mut(series) {
+            Some(series) => {
+                if series.samples.len() == capacity {
+                    series.samples.pop_front();
+                }
+                series.samples.push_back(value);
+            },
+            None => return
+        }
+        self.dirty = true;
+        let range = self.compute_range();
+        if range != self.range {
+            self.range = range;
+            self.labels_dirty = true;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for series in self.series.iter_mut() {
+            series.samples.clear();
+        }
+        self.dirty = true;
+    }
+
+    fn compute_range(&self) -> (f64, f64) {
+        if let Some(range) = self.y_range {
+            return range;
+        }
+        let samples = self.series.iter().flat_map(|series| series.samples.iter()).filter(|v| v.is_finite());
+        let (min, max) = samples.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(*v), max.max(*v)));
+        if min > max {
+            return (0.0, 1.0);
+        }
+        if (max - min).abs() < f64::EPSILON {
+            let pad = if min.abs() > 1.0 { min.abs() * 0.1 } else { 1.0 };
+            return (min - pad, max + pad);
+        }
+        // a little headroom so the lines don't ride the frame
+        let pad = (max - min) * 0.05;
+        (min - pad, max + pad)
+    }
+
+    // palette derived colors for series without their own
+    fn series_color(&self, index: usize) -> Color {
+        if let Some(color) = &self.series[index].color {
+            return color.clone();
+        }
+        let accent = self.style.outline_for(&self.gui_state, self.config).base_color();
+        if index == 0 {
+            return accent;
+        }
+        let (h, s, l) = accent.to_hsl();
+        let step = 360.0 / self.series.len().max(2) as f32;
+        Color::from_hsl(h + step * index as f32, s.max(0.5), l.clamp(0.35, 0.65))
+    }
+
+    // inside the frame
+    fn plot_area(&self) -> (i32, i32, i32, i32) {
+        let inset = self.style.border_for().max(0) + 1;
+        (self.x + inset, self.y + inset, (self.w - 2 * inset).max(1), (self.h - 2 * inset).max(1))
+    }
+
+    // frame and gridlines for each GuiState
+    pub fn gen_layers(&mut self)  {
+        let (plot_x, plot_y, plot_w, plot_h) = self.plot_area();
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+
+            // faint gridlines
+            let grid_color = self.style.outline_for(gui_state, self.config).base_color().with_alpha(64);
+            for row in 1..self.grid_rows {
+                let line_y = plot_y + plot_h * row as i32 / self.grid_rows as i32;
+                let line = Polyline::line(plot_x, line_y, plot_x + plot_w - 1, line_y, grid_color.clone());
+                self.layers.push(Layer::new(Box::new(line), false, group.clone()));
+            }
+            for column in 1..self.grid_columns {
+                let line_x = plot_x + plot_w * column as i32 / self.grid_columns as i32;
+                let line = Polyline::line(line_x, plot_y, line_x, plot_y + plot_h - 1, grid_color.clone());
+                self.layers.push(Layer::new(Box::new(line), false, group.clone()));
+            }
+        }
+    }
+
+    // one polyline per series, regenerated for every new sample
+    fn gen_plot_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let (plot_x, plot_y, plot_w, plot_h) = self.plot_area();
+        let (min, max) = self.range;
+        let span = if max > min { max - min } else { 1.0 };
+        for (index, series) in self.series.iter().enumerate() {
+            // newest sample on the right edge
+            let offset = self.capacity - series.samples.len();
+            let points: Vec<(i32, i32)> = series.samples.iter().enumerate()
+                .filter(|(_, v)| v.is_finite())
+                .map(|(i, v)| {
+                    let px = plot_x + ((offset + i) as i64 * (plot_w - 1) as i64 / (self.capacity - 1) as i64) as i32;
+                    let fraction = ((v - min) / span).clamp(0.0, 1.0);
+                    let py = plot_y + plot_h - 1 - (fraction * (plot_h - 1) as f64).round() as i32;
+                    (px, py)
+                })
+                .collect();
+            if points.is_empty() {
+                continue;
+            }
+            let line = Polyline::new(points, self.series_color(index));
+            layers.push(Layer::new(Box::new(line), self.state_layers.is_active(), self.plot_name.clone()));
+        }
+        layers
+    }
+
+    // axis labels, only regenerated when the y range changes
+    fn gen_label_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let (plot_x, plot_y, plot_w, plot_h) = self.plot_area();
+        let padding = self.style.padding_for(self.config);
+        let font_size = self.style.font_size_for(self.config.font_size);
+        let color = self.style.text_for(&self.gui_state, self.config);
+        if self.labels {
+            let (min, max) = self.range;
+            let values = [max, (min + max) / 2.0, min];
+            for (i, value) in values.iter().enumerate() {
+                let mut text = Text::new(
+                    plot_x,
+                    plot_y,
+                    font_size,
+                    format!("{:.*}{}", self.precision, value, self.unit),
+                    &self.config.font,
+                    color.clone(),
+                    padding
+                );
+                text.y = match i {
+                    0 => plot_y,
+                    1 => plot_y + (plot_h - text.h) / 2,
+                    _ => plot_y + plot_h - text.h
+                };
+                layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.label_name.clone()));
+            }
+        }
+        if let Some(x_label) = &self.x_label {
+            let mut text = Text::new(
+                plot_x,
+                plot_y,
+                font_size,
+                x_label.clone(),
+                &self.config.font,
+                color,
+                padding
+            );
+            text.x = plot_x + plot_w - text.w;
+            text.y = plot_y + plot_h - text.h;
+            layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.label_name.clone()));
+        }
+        layers
+    }
+}
+
+impl Gui for Chart {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_plot_layers());
+        canvas.layers.append(&mut self.gen_label_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.name[..]
+    }
+
+    // a single value goes to the first series
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+        self.push_value(0, value, canvas);
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        self.series.first().and_then(|series| series.samples.back().cloned())
+    }
+
+    #[allow(unused_variables)]
+    fn push_value(&mut self, series: usize, value: f64, canvas: &mut Canvas) {
+        self.push(series, value);
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+        self.labels_dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty && !self.labels_dirty {
+            return false;
+        }
+        if self.dirty {
+            canvas.replace_layer_group(self.plot_name.clone(), self.gen_plot_layers());
+        }
+        if self.labels_dirty {
+            canvas.replace_layer_group(self.label_name.clone(), self.gen_label_layers());
+        }
+        self.dirty = false;
+        self.labels_dirty = false;
+        true
+    }
+}
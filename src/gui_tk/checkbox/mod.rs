@@ -0,0 +1,61 @@
+use super::*;
+
+// Checkbox
+// square box with an optional label to its right
+// hat flips it and emits the event with "true" or "false"
+pub type Checkbox = Checked<CheckMark>;
+
+pub struct CheckMark;
+
+#[allow(dead_code)]
+impl Checkbox {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(checked: bool, text: String, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Checkbox {
+        Checked::build(checked, text, x, y, w, h, event, config)
+    }
+}
+
+impl CheckedLook for CheckMark {
+    const KIND: &'static str = "Checkbox";
+
+    // box, outline and label
+    fn gen_layers(checkbox: &Checkbox, gui_state: &GuiState) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let group = checkbox.state_layers.group(gui_state);
+        let mut layers = checkbox.state_layers.frame(gui_state, (checkbox.x, checkbox.y, checkbox.h, checkbox.h), checkbox.style.radius_for(), &checkbox.style, checkbox.config);
+        if !checkbox.text.is_empty() {
+            let padding = checkbox.style.padding_for(checkbox.config);
+            let mut text = Text::new(
+                checkbox.x + checkbox.h + padding as i32,
+                checkbox.y,
+                checkbox.style.font_size_for(checkbox.config.font_size),
+                checkbox.text.clone(),
+                &checkbox.config.font,
+                checkbox.style.text_for(gui_state, checkbox.config),
+                padding
+            );
+            if text.h < checkbox.h {
+                text.y = checkbox.y + (checkbox.h - text.h) / 2;
+            }
+            layers.push(Layer::new(Box::new(text), false, group));
+        }
+        layers
+    }
+
+    // the check mark
+    fn gen_mark_layers(checkbox: &Checkbox) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        if checkbox.checked {
+            let inset = checkbox.style.border_for().max(0) + 2;
+            let mark = Rect::new(
+                checkbox.x + inset,
+                checkbox.y + inset,
+                checkbox.h - 2 * inset,
+                checkbox.h - 2 * inset,
+                true,
+                checkbox.style.outline_for(&checkbox.gui_state, checkbox.config)
+            ).with_radius((checkbox.style.radius_for() - inset).max(0));
+            layers.push(Layer::new(Box::new(mark), checkbox.state_layers.is_active(), checkbox.mark_name.clone()));
+        }
+        layers
+    }
+}
@@ -0,0 +1,224 @@
+use super::*;
+use std::marker::PhantomData;
+
+// what sets a Toggle apart from a Checkbox, state and input are shared by Checked
+pub trait CheckedLook: Sized {
+    // start of the layer group names
+    const KIND: &'static str;
+
+    // the frame and anything else that only changes with the state
+    fn gen_layers(widget: &Checked<Self>, gui_state: &GuiState) -> Vec<Layer<Box<dyn Draw + Send>>>;
+
+    // the knob or mark, regenerated whenever checked or the state changes
+    fn gen_mark_layers(widget: &Checked<Self>) -> Vec<Layer<Box<dyn Draw + Send>>>;
+}
+
+// Checked
+// boolean widget, see Toggle and Checkbox
+// hat flips it and emits the event with "true" or "false"
+pub struct Checked<L: CheckedLook> {
+    pub checked: bool,
+    pub text: String,
+    pub event: Event,
+    pub name: &'static str,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    pub mark_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub gui_state: GuiState,
+    pub style: Style,
+    pub(super) config: &'static GuiConfig,
+    dirty: bool,
+    look: PhantomData<L>
+}
+
+#[allow(dead_code)]
+impl<L: CheckedLook> Checked<L> {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn build(checked: bool, text: String, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Checked<L> {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("{} - {}", L::KIND, uuid_string);
+        let mark_name = format!("{} - mark", layer_name);
+        let state_layers = StateLayers::new(&layer_name).with_live(&mark_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut widget = Checked {
+            checked,
+            text,
+            name: event.name,
+            event,
+            layer_name,
+            state_layers,
+            mark_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            gui_state: if checked { GuiState::Checked } else { GuiState::Base },
+            style: Style::new(),
+            config,
+            dirty: false,
+            look: PhantomData
+        };
+        widget.gen_layers();
+        widget
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Checked<L> {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            self.layers.append(&mut L::gen_layers(self, gui_state));
+        }
+    }
+
+    fn event(&self) -> Event {
+        let mut event = self.event.clone();
+        event.values = Some(vec![self.checked.to_string()]);
+        event
+    }
+}
+
+impl<L: CheckedLook> Gui for Checked<L> {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut L::gen_mark_layers(self));
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn set_text(&mut self, text: String, canvas: &mut Canvas) {
+        self.text = text;
+        self.reinit(canvas);
+    }
+
+    // the label, or the event name when there is none
+    fn get_text(&mut self) -> &str {
+        if self.text.is_empty() {
+            self.name
+        } else {
+            &self.text[..]
+        }
+    }
+
+    fn set_checked(&mut self, checked: bool, canvas: &mut Canvas) {
+        if checked == self.checked {
+            return;
+        }
+        self.checked = checked;
+        // only swap idle states, leave selected / clicked alone
+        match self.gui_state {
+            GuiState::Base | GuiState::Checked => {
+                let gui_state = self.idle_gui_state();
+                self.set_gui_state(gui_state, canvas);
+            },
+            _ => self.dirty = true
+        }
+    }
+
+    fn is_checked(&self) -> Option<bool> {
+        Some(self.checked)
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn idle_gui_state(&self) -> GuiState {
+        if self.checked { GuiState::Checked } else { GuiState::Base }
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.mark_name.clone(), L::gen_mark_layers(self));
+        true
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match h_e.action {
+            Action::Select => {
+                match h_e.io_state {
+                    IOState::Pressed => {
+                            self.checked = !self.checked;
+                            self.dirty = true;
+                            (false, Some(GuiState::Clicked), Some(self.event()))
+                        },
+                    IOState::Released => {
+                            (true, Some(GuiState::Selected), None)
+                        },
+                    _ => (false, None, None)
+                }
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
@@ -0,0 +1,390 @@
+use super::*;
+use std::time::Instant;
+
+// Zone
+// coloured band along the scale, e.g. the red line of a pressure gauge
+#[derive(Clone, Debug)]
+pub struct Zone {
+    pub from: f64,
+    pub to: f64,
+    pub color: Color
+}
+
+impl Zone {
+    pub fn new(from: f64, to: f64, color: Color) -> Zone {
+        Zone {from, to, color}
+    }
+}
+
+// Gauge
+// read only analog dial with a needle
+// the scale, ticks, labels and zones are drawn once per GuiState,
+// only the needle is redrawn as it eases towards a new value
+pub struct Gauge {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    // degrees clockwise from 12 o'clock
+    pub start_angle: f32,
+    pub sweep: f32,
+    pub major_ticks: usize,
+    pub minor_ticks: usize,
+    pub labels: bool,
+    pub precision: usize,
+    pub unit: &'static str,
+    pub show_value: bool,
+    pub zones: Vec<Zone>,
+    // seconds for the needle to cover most of the way to a new value, 0 jumps
+    pub settle_time: f32,
+    pub name: String,
+    pub state_layers: StateLayers,
+    pub needle_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub gui_state: GuiState,
+    pub style: Style,
+    needle: f64,
+    last_step: Option<Instant>,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl Gauge {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(value: f64, min: f64, max: f64, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> Gauge {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let name = format!("Gauge - {}", uuid_string);
+        let needle_name = format!("{} - needle", name);
+        let state_layers = StateLayers::new(&name).with_live(&needle_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let min_value = min.min(max);
+        let max_value = max.max(min);
+        let value = value.max(min_value).min(max_value);
+        let mut gauge = Gauge {
+            value,
+            min: min_value,
+            max: max_value,
+            start_angle: -135.0,
+            sweep: 270.0,
+            major_ticks: 5,
+            minor_ticks: 4,
+            labels: true,
+            precision: 0,
+            unit: "",
+            show_value: false,
+            zones: vec![],
+            settle_time: 0.3,
+            name,
+            state_layers,
+            needle_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            needle: value,
+            last_step: None,
+            dirty: false,
+            config
+        };
+        gauge.gen_layers();
+        gauge
+    }
+
+    // the part of the circle the scale covers
+    pub fn with_angles(mut self, start_angle: f32, sweep: f32) -> Gauge {
+        self.start_angle = start_angle;
+        self.sweep = sweep.clamp(1.0, 360.0);
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    // major ticks are labelled, minor ticks are drawn between them
+    pub fn with_ticks(mut self, major_ticks: usize, minor_ticks: usize, labels: bool) -> Gauge {
+        self.major_ticks = major_ticks;
+        self.minor_ticks = minor_ticks;
+        self.labels = labels;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    // label precision, with the value printed under the hub when show_value is set
+    pub fn with_format(mut self, precision: usize, unit: &'static str, show_value: bool) -> Gauge {
+        self.precision = precision;
+        self.unit = unit;
+        self.show_value = show_value;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn with_zone(mut self, zone: Zone) -> Gauge {
+        self.zones.push(zone);
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn with_settle_time(mut self, settle_time: f32) -> Gauge {
+        self.settle_time = settle_time.max(0.0);
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Gauge {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    fn center(&self) -> (i32, i32) {
+        (self.x + self.w / 2, self.y + self.h / 2)
+    }
+
+    fn radius(&self) -> i32 {
+        (self.w.min(self.h) / 2 - self.style.padding_for(self.config) as i32).max(4)
+    }
+
+    fn angle_for(&self, value: f64) -> f32 {
+        let fraction = if self.max > self.min {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.start_angle + self.sweep * fraction as f32
+    }
+
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}{}", self.precision, value, self.unit)
+    }
+
+    // face, scale, zones, ticks and labels for each GuiState
+    pub fn gen_layers(&mut self)  {
+        let (cx, cy) = self.center();
+        let radius = self.radius();
+        let tick = (radius / 8).max(3);
+        let font_size = self.style.font_size_for(self.config.font_size);
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            let color = self.style.outline_for(gui_state, self.config).base_color();
+            let face: Layer<Box<dyn Draw + Send>> = Layer::new(
+                Box::new(
+                    Rect::new(
+                        self.x,
+                        self.y,
+                        self.w,
+                        self.h,
+                        true,
+                        self.style.background_for(gui_state, self.config)
+                    ).with_radius(self.style.radius.unwrap_or(self.w.min(self.h) / 2))
+                ),
+                false,
+                group.clone()
+            );
+            self.layers.push(face);
+
+            for zone in self.zones.iter() {
+                let start = self.angle_for(zone.from.min(zone.to));
+                let end = self.angle_for(zone.from.max(zone.to));
+                let band = CircularArc::new(cx, cy, radius - 1, start, end - start, zone.color.clone()).with_thickness(tick);
+                self.layers.push(Layer::new(Box::new(band), false, group.clone()));
+            }
+
+            let scale = CircularArc::new(cx, cy, radius, self.start_angle, self.sweep, color.clone())
+                .with_thickness(self.style.border_for().max(1));
+            self.layers.push(Layer::new(Box::new(scale), false, group.clone()));
+
+            // ticks from the scale inwards, majors twice as long
+            let divisions = self.major_ticks.max(1) * (self.minor_ticks + 1);
+            for i in 0..=divisions {
+                let angle = self.start_angle + self.sweep * i as f32 / divisions as f32;
+                let major = i % (self.minor_ticks + 1) == 0;
+                let length = if major { tick * 2 } else { tick };
+                let (x1, y1) = point_on_circle(cx, cy, radius as f32, angle);
+                let (x2, y2) = point_on_circle(cx, cy, (radius - length) as f32, angle);
+                let mark = Polyline::line(x1, y1, x2, y2, color.clone()).with_thickness(if major { 2 } else { 1 });
+                self.layers.push(Layer::new(Box::new(mark), false, group.clone()));
+
+                if major && self.labels {
+                    // a full circle would print the first label twice
+                    if self.sweep >= 360.0 && i == divisions {
+                        continue;
+                    }
+                    let value = self.min + (self.max - self.min) * i as f64 / divisions as f64;
+                    let mut text = Text::new(
+                        cx,
+                        cy,
+                        font_size,
+                        self.format(value),
+                        &self.config.font,
+                        self.style.text_for(gui_state, self.config),
+                        0
+                    );
+                    let (lx, ly) = point_on_circle(cx, cy, (radius - tick * 2) as f32 - font_size * 0.75, angle);
+                    text.x = lx - text.w / 2;
+                    text.y = ly - text.h / 2;
+                    self.layers.push(Layer::new(Box::new(text), false, group.clone()));
+                }
+            }
+        }
+    }
+
+    // needle, hub and value readout
+    fn gen_needle_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let (cx, cy) = self.center();
+        let radius = self.radius();
+        let color = self.style.text_for(&self.gui_state, self.config);
+        let angle = self.angle_for(self.needle);
+        let (tip_x, tip_y) = point_on_circle(cx, cy, (radius - (radius / 8).max(3)) as f32, angle);
+        let needle = Polyline::line(cx, cy, tip_x, tip_y, color.clone()).with_thickness(2);
+        layers.push(Layer::new(Box::new(needle), self.state_layers.is_active(), self.needle_name.clone()));
+        let hub = (radius / 10).max(2);
+        let hub = Rect::new(cx - hub, cy - hub, hub * 2, hub * 2, true, color.clone()).with_radius(hub);
+        layers.push(Layer::new(Box::new(hub), self.state_layers.is_active(), self.needle_name.clone()));
+        if self.show_value {
+            let mut text = Text::new(
+                cx,
+                cy,
+                self.style.font_size_for(self.config.font_size),
+                self.format(self.value),
+                &self.config.font,
+                color,
+                0
+            );
+            text.x = cx - text.w / 2;
+            text.y = cy + radius / 3;
+            layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.needle_name.clone()));
+        }
+        layers
+    }
+
+    // ease the needle towards value, true while it is still moving
+    fn step_needle(&mut self) -> bool {
+        if (self.needle - self.value).abs() < f64::EPSILON {
+            self.last_step = None;
+            return false;
+        }
+        let now = Instant::now();
+        let elapsed = match self.last_step {
+            Some(last_step) => now.duration_since(last_step).as_secs_f32(),
+            None => 0.0
+        };
+        self.last_step = Some(now);
+        if self.settle_time <= 0.0 {
+            self.needle = self.value;
+            return true;
+        }
+        let factor = 1.0 - (-elapsed * 3.0 / self.settle_time).exp();
+        self.needle += (self.value - self.needle) * factor as f64;
+        // within half a degree is close enough
+        let close = (self.max - self.min).abs() / self.sweep.max(1.0) as f64 * 0.5;
+        if (self.needle - self.value).abs() <= close {
+            self.needle = self.value;
+        }
+        true
+    }
+}
+
+impl Gui for Gauge {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_needle_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.name[..]
+    }
+
+    #[allow(unused_variables)]
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+        let value = value.max(self.min).min(self.max);
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.dirty = true;
+        }
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        Some(self.value)
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    // keeps returning true until the needle settles so the view renders every frame
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        let moving = self.step_needle();
+        if !self.dirty && !moving {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.needle_name.clone(), self.gen_needle_layers());
+        true
+    }
+}
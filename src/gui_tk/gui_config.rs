@@ -1,20 +1,82 @@
 use super::*;
+use std::error::Error;
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
 
 
 #[allow(dead_code)]
-fn get_font(font: &'static str) -> FontVec {
-        let mut file = File::open(font).expect("Font File Not Found");
+fn get_font(font: &str) -> FontVec {
+    try_get_font(font).expect("Unable to Load Font File")
+}
+
+fn try_get_font(font: &str) -> Result<FontVec, Box<dyn Error>> {
+        let mut file = File::open(font)?;
         let mut font_data: Vec<u8> = vec![];
-        file.read_to_end(&mut font_data).expect("Unable to Read Font File");
-        FontVec::try_from_vec(font_data).unwrap()
+        file.read_to_end(&mut font_data)?;
+        Ok(FontVec::try_from_vec(font_data)?)
+}
+
+// Theme
+// the serializable half of a GuiConfig
+// everything a designer may want to tweak without rebuilding
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub font: String,
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    #[serde(default = "default_padding")]
+    pub padding: u32,
+    #[serde(default = "default_spacing")]
+    pub spacing: i32,
+    // tables go last so the theme can be written back out as toml
+    pub palette: Palette
+}
+
+fn default_font_size() -> f32 {
+    16.0
 }
 
+fn default_padding() -> u32 {
+    2
+}
+
+fn default_spacing() -> i32 {
+    4
+}
+
+impl Theme {
+    // .json files are read as json, anything else as toml
+    pub fn from_file(path: &str) -> Result<Theme, Box<dyn Error>> {
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Theme::from_json(&contents),
+            _ => Theme::from_toml(&contents)
+        }
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Theme, Box<dyn Error>> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_json(contents: &str) -> Result<Theme, Box<dyn Error>> {
+        Ok(serde_json::from_str(contents)?)
+    }
+}
+
+// configs leaked by GuiConfig::cached, by theme name
+static STATIC_CONFIGS: Mutex<Vec<(String, &'static GuiConfig)>> = Mutex::new(Vec::new());
+
 pub struct GuiConfig {
     pub palette: Palette,
-    pub font: FontVec
-
+    pub font: FontVec,
+    pub font_path: String,
+    pub font_size: f32,
+    pub padding: u32,
+    pub spacing: i32
 }
 
 #[allow(dead_code)]
@@ -22,7 +84,102 @@ impl GuiConfig {
     pub fn new(palette: Palette, font_path: &'static str) -> GuiConfig {
         let font = get_font(font_path);
         GuiConfig {
-            palette, font
+            palette,
+            font,
+            font_path: font_path.to_string(),
+            font_size: default_font_size(),
+            padding: default_padding(),
+            spacing: default_spacing()
         }
     }
+
+    pub fn from_theme(theme: Theme) -> Result<GuiConfig, Box<dyn Error>> {
+        let font = try_get_font(&theme.font)?;
+        Ok(GuiConfig {
+            palette: theme.palette,
+            font,
+            font_path: theme.font,
+            font_size: theme.font_size,
+            padding: theme.padding,
+            spacing: theme.spacing
+        })
+    }
+
+    pub fn from_theme_file(path: &str) -> Result<GuiConfig, Box<dyn Error>> {
+        GuiConfig::from_theme(Theme::from_file(path)?)
+    }
+
+    pub fn to_theme(&self) -> Theme {
+        Theme {
+            palette: self.palette.clone(),
+            font: self.font_path.clone(),
+            font_size: self.font_size,
+            padding: self.padding,
+            spacing: self.spacing
+        }
+    }
+
+    // widgets hold a &'static GuiConfig
+    // themes loaded at runtime are leaked to live as long as the app
+    // every call leaks another config, fonts and all, so build each theme once and reuse it
+    // or switch themes with cached
+    pub fn into_static(self) -> &'static GuiConfig {
+        Box::leak(Box::new(self))
+    }
+
+    // one leaked config per name, load only runs the first time the name is asked for
+    // so toggling between themes at runtime leaks each of them once
+    pub fn cached<F>(name: &str, load: F) -> Result<&'static GuiConfig, Box<dyn Error>>
+    where F: FnOnce() -> Result<GuiConfig, Box<dyn Error>> {
+        let mut configs = STATIC_CONFIGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((_, config)) = configs.iter().find(|(cached, _)| cached == name) {
+            return Ok(*config);
+        }
+        let config = load()?.into_static();
+        configs.push((name.to_string(), config));
+        Ok(config)
+    }
+
+    // a theme file through cached, by path
+    pub fn from_theme_file_cached(path: &str) -> Result<&'static GuiConfig, Box<dyn Error>> {
+        GuiConfig::cached(path, || GuiConfig::from_theme_file(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme() -> Theme {
+        Theme {
+            font: "assets/fonts/Nanum_Gothic/NanumGothic-Regular.ttf".to_string(),
+            font_size: 20.0,
+            padding: 3,
+            spacing: 6,
+            palette: Palette::from_accent(Color::from_hsl(210.0, 0.6, 0.5))
+        }
+    }
+
+    #[test]
+    fn themes_round_trip_through_toml() {
+        let written = toml::to_string(&theme()).unwrap();
+        let read = Theme::from_toml(&written).unwrap();
+        assert_eq!(read.font_size, 20.0);
+        assert_eq!(toml::to_string(&read).unwrap(), written);
+    }
+
+    #[test]
+    fn themes_round_trip_through_json() {
+        let written = serde_json::to_string(&theme()).unwrap();
+        let read = Theme::from_json(&written).unwrap();
+        assert_eq!(read.spacing, 6);
+        assert_eq!(serde_json::to_string(&read).unwrap(), written);
+    }
+
+    #[test]
+    fn malformed_themes_are_an_error() {
+        assert!(Theme::from_toml("font = ").is_err());
+        assert!(Theme::from_toml("font_size = 12.0").is_err());
+        assert!(Theme::from_json("{\"font\": 12}").is_err());
+    }
 }
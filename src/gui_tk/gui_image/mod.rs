@@ -0,0 +1,231 @@
+use super::*;
+use image::DynamicImage;
+use std::error::Error;
+use std::sync::Arc;
+
+// GuiImage
+// navigable image button / icon
+// selected and clicked show the alternate image if there is one,
+// otherwise the image gets a state colored outline
+pub struct GuiImage {
+    pub path: &'static str,
+    pub event: Event,
+    pub name: &'static str,
+    pub alternate_path: Option<&'static str>,
+    // decoded once, shared between the state layers
+    image: Arc<DynamicImage>,
+    alternate: Option<Arc<DynamicImage>>,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub gui_state: GuiState,
+    pub style: Style,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl GuiImage {
+    pub fn new(path: &'static str, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> GuiImage {
+        GuiImage::try_new(path, x, y, w, h, event, config).expect("Image File Not Found")
+    }
+
+    // new, with an error rather than a panic when the file is missing or won't decode
+    pub fn try_new(path: &'static str, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Result<GuiImage, Box<dyn Error>> {
+        let image = GuiImage::load(path)?;
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("GuiImage - {}", uuid_string);
+        let state_layers = StateLayers::new(&layer_name);
+
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut gui_image = GuiImage {
+            path,
+            name: event.name,
+            event,
+            alternate_path: None,
+            image,
+            alternate: None,
+            layer_name,
+            state_layers,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            config
+        };
+        gui_image.gen_layers();
+        Ok(gui_image)
+    }
+
+    // decode an image file
+    pub fn load(path: &str) -> Result<Arc<DynamicImage>, Box<dyn Error>> {
+        match image::open(path) {
+            Ok(image) => Ok(Arc::new(image)),
+            Err(error) => Err(format!("Image {} could not be loaded: {}", path, error).into())
+        }
+    }
+
+    // image shown while selected or clicked instead of an outline
+    pub fn with_alternate(self, alternate_path: &'static str) -> GuiImage {
+        self.try_with_alternate(alternate_path).expect("Image File Not Found")
+    }
+
+    pub fn try_with_alternate(mut self, alternate_path: &'static str) -> Result<GuiImage, Box<dyn Error>> {
+        self.alternate = Some(GuiImage::load(alternate_path)?);
+        self.alternate_path = Some(alternate_path);
+        self.layers = vec![];
+        self.gen_layers();
+        Ok(self)
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> GuiImage {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        self.activate(canvas);
+    }
+
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            let highlighted = matches!(gui_state, GuiState::Selected | GuiState::Clicked);
+            let shown = match (&self.alternate, highlighted) {
+                (Some(alternate), true) => alternate.clone(),
+                _ => self.image.clone()
+            };
+            let img: Layer<Box<dyn Draw + Send>> = Layer::new(
+                Box::new(Image::from_shared(shown, self.x, self.y, self.w, self.h, 0, 0)),
+                false,
+                group.clone()
+            );
+            self.layers.push(img);
+
+            // grey the image out
+            if let GuiState::Disabled = gui_state {
+                let overlay = self.style.background_for(gui_state, self.config).base_color().with_alpha(160);
+                let overlay: Layer<Box<dyn Draw + Send>> = Layer::new(
+                    Box::new(Rect::new(self.x, self.y, self.w, self.h, true, overlay)),
+                    false,
+                    group.clone()
+                );
+                self.layers.push(overlay);
+            }
+
+            // outline every state but base, unless an alternate image marks selection
+            let outlined = match gui_state {
+                GuiState::Base => false,
+                _ => !(highlighted && self.alternate.is_some())
+            };
+            if outlined && self.style.border_for() > 0 {
+                let outline: Layer<Box<dyn Draw + Send>> = Layer::new(
+                    Box::new(
+                        Rect::new(
+                            self.x,
+                            self.y,
+                            self.w,
+                            self.h,
+                            false,
+                            self.style.outline_for(gui_state, self.config)
+                        ).with_radius(self.style.radius_for()).with_border(self.style.border_for())
+                    ),
+                    false,
+                    group.clone()
+                );
+                self.layers.push(outline);
+            }
+        }
+    }
+}
+
+impl Gui for GuiImage {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        //set the correct layers to active
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str{
+        self.name
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        self.activate(canvas);
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match h_e.action {
+            // handle hat press
+            Action::Select => {
+                match h_e.io_state {
+                    IOState::Pressed => {
+                            (false, Some(GuiState::Clicked), Some(self.event.clone()))
+                        },
+                    IOState::Released => {
+                            (true, Some(GuiState::Selected), None)
+                        },
+                    _ => (false, None, None)
+                }
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
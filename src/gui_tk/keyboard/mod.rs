@@ -0,0 +1,518 @@
+use super::*;
+
+const LOWER: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const UPPER: [&str; 4] = ["1234567890", "QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+const SYMBOLS: [&str; 4] = ["!@#$%^&*()", "-_=+[]{}\\|", ";:'\",.<>/?", "`~"];
+// widest row, in key widths
+const COLUMNS: i32 = 10;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Key {
+    Char(char),
+    Shift,
+    Symbols,
+    Space,
+    Backspace,
+    Cancel,
+    Ok
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyboardMode {
+    Lower,
+    Upper,
+    Symbols
+}
+
+impl KeyboardMode {
+    fn all() -> [KeyboardMode; 3] {
+        [KeyboardMode::Lower, KeyboardMode::Upper, KeyboardMode::Symbols]
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            KeyboardMode::Lower => "lower",
+            KeyboardMode::Upper => "upper",
+            KeyboardMode::Symbols => "symbols"
+        }
+    }
+}
+
+// a key and where it sits on screen
+#[derive(Clone, Debug)]
+struct KeyCap {
+    key: Key,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32
+}
+
+// Keyboard
+// text field over a grid of keys, for text entry with the d-pad
+// hat opens it, the arrows move between keys and hat types the highlighted one
+// OK emits the event with the entered text, Cancel or back restores the old text
+pub struct Keyboard {
+    pub text: String,
+    pub max_length: usize,
+    pub event: Event,
+    pub name: &'static str,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    pub field_name: String,
+    pub highlight_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub mode: KeyboardMode,
+    pub editing: bool,
+    pub gui_state: GuiState,
+    pub style: Style,
+    row: usize,
+    column: usize,
+    original: String,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl Keyboard {
+    pub fn new(text: String, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Keyboard {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("Keyboard - {}", uuid_string);
+        let field_name = format!("{} - field", layer_name);
+        let highlight_name = format!("{} - highlight", layer_name);
+        let state_layers = StateLayers::new(&layer_name).with_live(&field_name).with_live(&highlight_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut keyboard = Keyboard {
+            original: text.clone(),
+            text,
+            max_length: 64,
+            name: event.name,
+            event,
+            layer_name,
+            state_layers,
+            field_name,
+            highlight_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            mode: KeyboardMode::Lower,
+            editing: false,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            row: 1,
+            column: 0,
+            dirty: false,
+            config
+        };
+        keyboard.gen_layers();
+        keyboard
+    }
+
+    pub fn with_max_length(mut self, max_length: usize) -> Keyboard {
+        self.max_length = max_length;
+        self.text = self.text.chars().take(max_length).collect();
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Keyboard {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+        for mode in KeyboardMode::all().iter() {
+            canvas.drop_layer_group(self.keys_name(mode));
+        }
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    fn keys_name(&self, mode: &KeyboardMode) -> String {
+        format!("{} - keys - {}", self.layer_name, mode.suffix())
+    }
+
+    fn field_h(&self) -> i32 {
+        self.style.font_size_for(self.config.font_size) as i32 + 2 * self.style.padding_for(self.config) as i32 + 2
+    }
+
+    // rows of characters, then the special keys
+    fn key_caps(&self, mode: &KeyboardMode) -> Vec<Vec<KeyCap>> {
+        let rows = match mode {
+            KeyboardMode::Lower => LOWER,
+            KeyboardMode::Upper => UPPER,
+            KeyboardMode::Symbols => SYMBOLS
+        };
+        let top = self.y + self.field_h();
+        let key_w = self.w / COLUMNS;
+        let key_h = (self.h - self.field_h()) / (rows.len() as i32 + 1);
+        let mut caps: Vec<Vec<KeyCap>> = vec![];
+        for (r, row) in rows.iter().enumerate() {
+            let count = row.chars().count() as i32;
+            // short rows are centered
+            let left = self.x + (COLUMNS - count) * key_w / 2;
+            caps.push(row.chars().enumerate().map(|(c, ch)| KeyCap {
+                key: Key::Char(ch),
+                x: left + c as i32 * key_w,
+                y: top + r as i32 * key_h,
+                w: key_w,
+                h: key_h
+            }).collect());
+        }
+        let specials = [(Key::Shift, 2), (Key::Symbols, 2), (Key::Space, 2), (Key::Backspace, 2), (Key::Cancel, 1), (Key::Ok, 1)];
+        let mut left = self.x;
+        let bottom = top + rows.len() as i32 * key_h;
+        caps.push(specials.iter().map(|(key, span)| {
+            let cap = KeyCap {key: key.clone(), x: left, y: bottom, w: key_w * span, h: key_h};
+            left += key_w * span;
+            cap
+        }).collect());
+        caps
+    }
+
+    fn label(key: &Key, mode: &KeyboardMode) -> String {
+        match key {
+            Key::Char(ch) => ch.to_string(),
+            Key::Shift => "Shift".to_string(),
+            Key::Symbols => if *mode == KeyboardMode::Symbols { "abc".to_string() } else { "#+=".to_string() },
+            Key::Space => "Space".to_string(),
+            Key::Backspace => "Del".to_string(),
+            Key::Cancel => "X".to_string(),
+            Key::Ok => "OK".to_string()
+        }
+    }
+
+    fn current_key(&self) -> Option<KeyCap> {
+        self.key_caps(&self.mode).get(self.row).and_then(|row| row.get(self.column)).cloned()
+    }
+
+    // left / right wrap within the row
+    fn move_column(&mut self, amount: i32) {
+        let caps = self.key_caps(&self.mode);
+        let count = caps[self.row].len() as i32;
+        self.column = (self.column as i32 + amount).rem_euclid(count) as usize;
+        self.dirty = true;
+    }
+
+    // up / down land on the key under the middle of the current one
+    fn move_row(&mut self, amount: i32) {
+        let caps = self.key_caps(&self.mode);
+        let row = self.row as i32 + amount;
+        if row < 0 || row >= caps.len() as i32 {
+            return;
+        }
+        let current = &caps[self.row][self.column.min(caps[self.row].len() - 1)];
+        let middle = current.x + current.w / 2;
+        let target = &caps[row as usize];
+        let column = target.iter().position(|cap| middle >= cap.x && middle < cap.x + cap.w)
+            .unwrap_or(if middle < target[0].x { 0 } else { target.len() - 1 });
+        self.row = row as usize;
+        self.column = column;
+        self.dirty = true;
+    }
+
+    fn set_mode(&mut self, mode: KeyboardMode) {
+        self.mode = mode;
+        let rows = self.key_caps(&self.mode);
+        self.column = self.column.min(rows[self.row].len() - 1);
+        self.dirty = true;
+    }
+
+    // returns the event for OK, Some(None) for keys that close without one
+    fn press(&mut self, key: &Key) -> Option<Option<Event>> {
+        match key {
+            Key::Char(ch) => {
+                if self.text.chars().count() < self.max_length {
+                    self.text.push(*ch);
+                }
+                // shift only lasts for one character
+                if self.mode == KeyboardMode::Upper {
+                    self.set_mode(KeyboardMode::Lower);
+                }
+            },
+            Key::Space => {
+                if self.text.chars().count() < self.max_length {
+                    self.text.push(' ');
+                }
+            },
+            Key::Backspace => {
+                self.text.pop();
+            },
+            Key::Shift => {
+                let mode = if self.mode == KeyboardMode::Upper { KeyboardMode::Lower } else { KeyboardMode::Upper };
+                self.set_mode(mode);
+            },
+            Key::Symbols => {
+                let mode = if self.mode == KeyboardMode::Symbols { KeyboardMode::Lower } else { KeyboardMode::Symbols };
+                self.set_mode(mode);
+            },
+            Key::Ok => {
+                self.editing = false;
+                self.dirty = true;
+                let mut event = self.event.clone();
+                event.values = Some(vec![self.text.clone()]);
+                return Some(Some(event));
+            },
+            Key::Cancel => {
+                self.cancel();
+                return Some(None);
+            }
+        }
+        self.dirty = true;
+        None
+    }
+
+    fn cancel(&mut self) {
+        self.editing = false;
+        self.text = self.original.clone();
+        self.set_mode(KeyboardMode::Lower);
+    }
+
+    // frame and field box for each GuiState
+    pub fn gen_layers(&mut self)  {
+        let field_h = self.field_h();
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+            if self.style.border_for() > 0 {
+                let field: Layer<Box<dyn Draw + Send>> = Layer::new(
+                    Box::new(Rect::new(self.x, self.y, self.w, field_h, false, self.style.outline_for(gui_state, self.config))),
+                    false,
+                    group.clone()
+                );
+                self.layers.push(field);
+            }
+        }
+
+        // every mode's keys are drawn up front and swapped by group
+        let padding = self.style.padding_for(self.config);
+        let font_size = self.style.font_size_for(self.config.font_size);
+        let color = self.style.text_for(&GuiState::Base, self.config);
+        let outline = self.style.outline_for(&GuiState::Base, self.config);
+        for mode in KeyboardMode::all().iter() {
+            let group = self.keys_name(mode);
+            for cap in self.key_caps(mode).iter().flatten() {
+                let frame = Rect::new(cap.x + 1, cap.y + 1, cap.w - 2, cap.h - 2, false, outline.clone())
+                    .with_radius(self.style.radius_for().min(cap.h / 4));
+                self.layers.push(Layer::new(Box::new(frame), false, group.clone()));
+                let mut text = Text::new(cap.x, cap.y, font_size, Keyboard::label(&cap.key, mode), &self.config.font, color.clone(), padding);
+                text.x = cap.x + (cap.w - text.w) / 2;
+                text.y = cap.y + (cap.h - text.h) / 2;
+                self.layers.push(Layer::new(Box::new(text), false, group.clone()));
+            }
+        }
+    }
+
+    // entered text with a cursor
+    fn gen_field_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let padding = self.style.padding_for(self.config);
+        let cursor = if self.editing { "_" } else { "" };
+        let mut text = Text::new(
+            self.x + padding as i32,
+            self.y + 1,
+            self.style.font_size_for(self.config.font_size),
+            format!("{}{}", self.text, cursor),
+            &self.config.font,
+            self.style.text_for(&self.gui_state, self.config),
+            padding
+        );
+        // keep the end of long text in view
+        if text.w > self.w - 2 * padding as i32 {
+            text.x = self.x + self.w - padding as i32 - text.w;
+        }
+        vec![Layer::new(Box::new(text), self.state_layers.is_active(), self.field_name.clone())]
+    }
+
+    // translucent box over the highlighted key while editing
+    fn gen_highlight_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        if !self.editing {
+            return layers;
+        }
+        if let Some(cap) = self.current_key() {
+            let color = self.style.background_for(&GuiState::Selected, self.config).base_color().with_alpha(128);
+            let highlight = Rect::new(cap.x + 1, cap.y + 1, cap.w - 2, cap.h - 2, true, color)
+                .with_radius(self.style.radius_for().min(cap.h / 4));
+            layers.push(Layer::new(Box::new(highlight), self.state_layers.is_active(), self.highlight_name.clone()));
+            let outline = Rect::new(cap.x + 1, cap.y + 1, cap.w - 2, cap.h - 2, false, self.style.outline_for(&GuiState::Selected, self.config))
+                .with_radius(self.style.radius_for().min(cap.h / 4)).with_border(2);
+            layers.push(Layer::new(Box::new(outline), self.state_layers.is_active(), self.highlight_name.clone()));
+        }
+        layers
+    }
+
+    fn activate_keys(&self, canvas: &mut Canvas) {
+        for mode in KeyboardMode::all().iter() {
+            if *mode == self.mode {
+                canvas.activate_layer_group(self.keys_name(mode));
+            } else {
+                canvas.deactivate_layer_group(self.keys_name(mode));
+            }
+        }
+    }
+}
+
+impl Gui for Keyboard {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_field_layers());
+        canvas.layers.append(&mut self.gen_highlight_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        self.activate_keys(canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        for mode in KeyboardMode::all().iter() {
+            canvas.deactivate_layer_group(self.keys_name(mode));
+        }
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        let mut groups = self.state_layers.groups();
+        for mode in KeyboardMode::all().iter() {
+            groups.push(self.keys_name(mode));
+        }
+        groups
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    // store updates are ignored while the user is typing
+    #[allow(unused_variables)]
+    fn set_text(&mut self, text: String, canvas: &mut Canvas) {
+        if self.editing {
+            return;
+        }
+        self.text = text.chars().take(self.max_length).collect();
+        self.original = self.text.clone();
+        self.dirty = true;
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.text[..]
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        if self.state_layers.is_active() {
+            self.activate_keys(canvas);
+        }
+        canvas.replace_layer_group(self.field_name.clone(), self.gen_field_layers());
+        canvas.replace_layer_group(self.highlight_name.clone(), self.gen_highlight_layers());
+        true
+    }
+
+    fn cancel_edit(&mut self) -> bool {
+        if !self.editing {
+            return false;
+        }
+        self.cancel();
+        true
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match (self.editing, h_e.action, &h_e.io_state) {
+            // start typing
+            (false, Action::Select, IOState::Pressed) => {
+                self.editing = true;
+                self.original = self.text.clone();
+                self.dirty = true;
+                (false, Some(GuiState::Clicked), None)
+            },
+            // release after OK hands control back
+            (false, Action::Select, IOState::Released) => (true, Some(GuiState::Selected), None),
+            // type the highlighted key
+            (true, Action::Select, IOState::Pressed) => {
+                let key = match self.current_key() {
+                    Some(cap) => cap.key,
+                    None => return (false, None, None)
+                };
+                match self.press(&key) {
+                    Some(Some(event)) => (false, Some(GuiState::Clicked), Some(event)),
+                    Some(None) => (true, Some(GuiState::Selected), None),
+                    None => (false, None, None)
+                }
+            },
+            // back works like Cancel
+            (true, Action::Back, IOState::Pressed) => {
+                self.cancel();
+                (true, Some(GuiState::Selected), None)
+            },
+            (true, Action::Left, IOState::Pressed) | (true, Action::Left, IOState::Repeated) => {
+                self.move_column(-1);
+                (false, None, None)
+            },
+            (true, Action::Right, IOState::Pressed) | (true, Action::Right, IOState::Repeated) => {
+                self.move_column(1);
+                (false, None, None)
+            },
+            (true, Action::Up, IOState::Pressed) | (true, Action::Up, IOState::Repeated) => {
+                self.move_row(-1);
+                (false, None, None)
+            },
+            (true, Action::Down, IOState::Pressed) | (true, Action::Down, IOState::Repeated) => {
+                self.move_row(1);
+                (false, None, None)
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
@@ -0,0 +1,313 @@
+use super::*;
+
+// Menu
+// scrolling list of MenuItems
+// hat opens the menu, up / down move the highlight,
+// hat again emits the highlighted item's event, back closes it
+pub struct Menu {
+    pub items: Vec<MenuItem>,
+    pub event: Event,
+    pub name: &'static str,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    pub items_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub item_h: i32,
+    pub highlighted: usize,
+    pub scroll: usize,
+    pub open: bool,
+    pub gui_state: GuiState,
+    pub style: Style,
+    // set by with_item_height, otherwise item_h follows the config's font
+    fixed_item_h: bool,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+pub struct MenuItem {
+    pub text: &'static str,
+    pub event: Event,
+    pub name: &'static str
+}
+
+impl MenuItem {
+    pub fn new(text: &'static str, event: Event, name: &'static str) -> MenuItem {
+        MenuItem {
+            text, event, name
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Menu {
+    pub fn new(items: Vec<MenuItem>, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Menu {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("Menu - {}", uuid_string);
+        let items_name = format!("{} - items", layer_name);
+        let state_layers = StateLayers::new(&layer_name).with_live(&items_name);
+        let item_h = Menu::default_item_h(config);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut menu = Menu {
+            items,
+            name: event.name,
+            event,
+            layer_name,
+            state_layers,
+            items_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            item_h,
+            highlighted: 0,
+            scroll: 0,
+            open: false,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            fixed_item_h: false,
+            dirty: false,
+            config
+        };
+        menu.gen_layers();
+        menu
+    }
+
+    pub fn with_item_height(mut self, item_h: i32) -> Menu {
+        self.item_h = item_h.max(1);
+        self.fixed_item_h = true;
+        self
+    }
+
+    fn default_item_h(config: &GuiConfig) -> i32 {
+        (config.font_size as i32 + 2 * config.padding as i32).max(1)
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Menu {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    pub fn visible_items(&self) -> usize {
+        (self.h / self.item_h).max(1) as usize
+    }
+
+    pub fn highlighted_item(&self) -> Option<&MenuItem> {
+        self.items.get(self.highlighted)
+    }
+
+    // move the highlight, scrolling to keep it in view
+    pub fn move_highlight(&mut self, amount: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let last = self.items.len() as isize - 1;
+        let highlighted = (self.highlighted as isize + amount).max(0).min(last) as usize;
+        if highlighted == self.highlighted {
+            return;
+        }
+        self.highlighted = highlighted;
+        self.scroll_to_highlight();
+        self.dirty = true;
+    }
+
+    fn scroll_to_highlight(&mut self) {
+        let visible = self.visible_items();
+        if self.highlighted < self.scroll {
+            self.scroll = self.highlighted;
+        } else if self.highlighted >= self.scroll + visible {
+            self.scroll = self.highlighted + 1 - visible;
+        }
+    }
+
+    // frame: background and outline for each GuiState
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+        }
+    }
+
+    // the visible rows, highlight and scroll bar
+    // regenerated whenever the highlight, scroll or state changes
+    fn gen_item_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let visible = self.visible_items();
+        let padding = self.style.padding_for(self.config);
+        let font_size = self.style.font_size_for(self.config.font_size);
+        let end = (self.scroll + visible).min(self.items.len());
+        for (row, index) in (self.scroll..end).enumerate() {
+            let item_y = self.y + row as i32 * self.item_h;
+            let highlighted = self.open && index == self.highlighted;
+            let text_color = if highlighted {
+                let highlight = Rect::new(self.x + 1, item_y, self.w - 2, self.item_h, true, self.style.background_for(&GuiState::Selected, self.config));
+                layers.push(Layer::new(Box::new(highlight), self.state_layers.is_active(), self.items_name.clone()));
+                self.style.text_for(&GuiState::Selected, self.config)
+            } else {
+                self.style.text_for(&self.gui_state, self.config)
+            };
+            let mut text = Text::new(
+                self.x + padding as i32,
+                item_y,
+                font_size,
+                self.items[index].text.to_string(),
+                &self.config.font,
+                text_color,
+                padding
+            );
+            if text.h < self.item_h {
+                text.y = item_y + (self.item_h - text.h) / 2;
+            }
+            layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.items_name.clone()));
+        }
+
+        // scroll bar when not every item fits
+        if self.items.len() > visible {
+            let thumb_h = (self.h * visible as i32 / self.items.len() as i32).max(2);
+            let thumb_y = self.y + self.h * self.scroll as i32 / self.items.len() as i32;
+            let thumb = Rect::new(self.x + self.w - 4, thumb_y, 3, thumb_h, true, self.style.outline_for(&self.gui_state, self.config));
+            layers.push(Layer::new(Box::new(thumb), self.state_layers.is_active(), self.items_name.clone()));
+        }
+        layers
+    }
+}
+
+impl Gui for Menu {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_item_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        // a different font size changes how many items fit
+        if !self.fixed_item_h {
+            self.item_h = Menu::default_item_h(config);
+            self.scroll_to_highlight();
+        }
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        match self.items.get(self.highlighted) {
+            Some(item) => item.text,
+            None => ""
+        }
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.items_name.clone(), self.gen_item_layers());
+        true
+    }
+
+    fn cancel_edit(&mut self) -> bool {
+        if !self.open {
+            return false;
+        }
+        self.open = false;
+        self.dirty = true;
+        true
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match (self.open, h_e.action, &h_e.io_state) {
+            // open the menu
+            (false, Action::Select, IOState::Pressed) => {
+                self.open = true;
+                self.dirty = true;
+                (false, Some(GuiState::Clicked), Some(self.event.clone()))
+            },
+            // release after choosing an item hands control back
+            (false, Action::Select, IOState::Released) => (true, Some(GuiState::Selected), None),
+            // choose the highlighted item
+            (true, Action::Select, IOState::Pressed) => {
+                self.open = false;
+                self.dirty = true;
+                let event = self.items.get(self.highlighted).map(|item| item.event.clone());
+                (false, Some(GuiState::Clicked), event)
+            },
+            // back closes the menu without choosing
+            (true, Action::Back, IOState::Pressed) => {
+                self.open = false;
+                self.dirty = true;
+                (true, Some(GuiState::Selected), None)
+            },
+            (true, Action::Up, IOState::Pressed) | (true, Action::Up, IOState::Repeated) => {
+                self.move_highlight(-1);
+                (false, None, None)
+            },
+            (true, Action::Down, IOState::Pressed) | (true, Action::Down, IOState::Repeated) => {
+                self.move_highlight(1);
+                (false, None, None)
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
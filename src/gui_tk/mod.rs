@@ -2,11 +2,10 @@ use serde::{Serialize, Deserialize};
 
 use uuid::Uuid;
 
-use super::canvas::{Rect, Layer, Draw, Canvas, Text};
-use super::fb::Color;
+use super::canvas::{Rect, Layer, Draw, Canvas, Text, Image, Polyline, CircularArc, point_on_circle};
+use super::fb::{Color, Paint};
 
-use super::hid::HIDEvent; 
-use super::hid::IOState;
+use super::hid::{InputEvent, IOState, Action};
 
 mod button;
 #[allow(unused_imports)]
@@ -19,70 +18,246 @@ pub use block::*;
 mod text_block;
 #[allow(unused_imports)]
 pub use text_block::*;
+
+mod menu;
+pub use menu::*;
+
+mod gui_image;
+pub use gui_image::*;
+
+mod spinner;
+pub use spinner::*;
+
+mod checked;
+pub use checked::*;
+
+mod toggle;
+pub use toggle::*;
+
+mod checkbox;
+pub use checkbox::*;
+
+mod progress_bar;
+pub use progress_bar::*;
+
+mod slider;
+pub use slider::*;
+
+mod chart;
+pub use chart::*;
+
+mod gauge;
+pub use gauge::*;
+
+mod keyboard;
+pub use keyboard::*;
 use glyph_brush_layout::*;
 use ab_glyph::*;
 
 mod gui_config;
 pub use gui_config::*;
 
+mod style;
+pub use style::*;
+
+mod state_layers;
+pub use state_layers::*;
+
+mod stepped;
+use stepped::*;
+
 
 use log::*;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GuiState{
     Base,
     Clicked,
-    Selected
+    Selected,
+    // greyed out and skipped by View navigation
+    Disabled,
+    // toggled on while not selected
+    Checked
+}
+
+impl GuiState {
+    // every state a gui object may need layers for
+    pub fn all() -> Vec<GuiState> {
+        vec![
+            GuiState::Base,
+            GuiState::Clicked,
+            GuiState::Selected,
+            GuiState::Disabled,
+            GuiState::Checked
+        ]
+    }
+
+    pub fn group_suffix(&self) -> &'static str {
+        match self {
+            GuiState::Base => "regular",
+            GuiState::Clicked => "clicked",
+            GuiState::Selected => "selected",
+            GuiState::Disabled => "disabled",
+            GuiState::Checked => "checked"
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self != GuiState::Disabled
+    }
+}
+
+// direction bars and sliders fill in
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Orientation {
+    Horizontal,
+    Vertical
 }
 
 //color pallete
-#[derive(Clone)]
+// outlines and backgrounds take any Paint, text is always a flat Color
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Palette {
-    base: Color,
+    base: Paint,
     base_text: Color,
-    base_background: Color,
+    base_background: Paint,
 
-    selected: Color,
+    selected: Paint,
     selected_text: Color,
-    selected_background: Color,
+    selected_background: Paint,
 
-    clicked: Color,
+    clicked: Paint,
     clicked_text: Color,
-    clicked_background: Color,
-
+    clicked_background: Paint,
+
+    // optional, derived from the entries above when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disabled: Option<Paint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disabled_text: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disabled_background: Option<Paint>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checked: Option<Paint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checked_text: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checked_background: Option<Paint>,
 }
 
 #[allow(dead_code)]
 impl Palette {
     pub fn new(
-        base: Color, 
+        base: impl Into<Paint>, 
         base_text: Color, 
-        base_background: Color,
+        base_background: impl Into<Paint>,
 
-        selected: Color, 
+        selected: impl Into<Paint>, 
         selected_text: Color, 
-        selected_background: Color,
+        selected_background: impl Into<Paint>,
 
-        clicked: Color, 
+        clicked: impl Into<Paint>, 
         clicked_text: Color, 
-        clicked_background: Color
+        clicked_background: impl Into<Paint>
 
     ) -> Palette {
         Palette {
-            base,
+            base: base.into(),
             base_text,
-            base_background,
+            base_background: base_background.into(),
 
-            selected,
+            selected: selected.into(),
             selected_text,
-            selected_background,
+            selected_background: selected_background.into(),
 
-            clicked,
+            clicked: clicked.into(),
             clicked_text,
-            clicked_background,
+            clicked_background: clicked_background.into(),
+
+            disabled: None,
+            disabled_text: None,
+            disabled_background: None,
+
+            checked: None,
+            checked_text: None,
+            checked_background: None,
+        }
+    }
+
+    pub fn with_disabled(mut self, disabled: impl Into<Paint>, disabled_text: Color, disabled_background: impl Into<Paint>) -> Palette {
+        self.disabled = Some(disabled.into());
+        self.disabled_text = Some(disabled_text);
+        self.disabled_background = Some(disabled_background.into());
+        self
+    }
+
+    pub fn with_checked(mut self, checked: impl Into<Paint>, checked_text: Color, checked_background: impl Into<Paint>) -> Palette {
+        self.checked = Some(checked.into());
+        self.checked_text = Some(checked_text);
+        self.checked_background = Some(checked_background.into());
+        self
+    }
+
+    // disabled falls back to a greyed out base, checked to clicked
+    pub fn outline(&self, gui_state: &GuiState) -> Paint {
+        match gui_state {
+            GuiState::Base => self.base.clone(),
+            GuiState::Clicked => self.clicked.clone(),
+            GuiState::Selected => self.selected.clone(),
+            GuiState::Disabled => self.disabled.clone().unwrap_or_else(|| greyed(&self.base)),
+            GuiState::Checked => self.checked.clone().unwrap_or_else(|| self.clicked.clone())
         }
     }
+
+    pub fn text(&self, gui_state: &GuiState) -> Color {
+        match gui_state {
+            GuiState::Base => self.base_text.clone(),
+            GuiState::Clicked => self.clicked_text.clone(),
+            GuiState::Selected => self.selected_text.clone(),
+            GuiState::Disabled => self.disabled_text.clone().unwrap_or_else(|| self.base_text.mix(&grey(), 0.6)),
+            GuiState::Checked => self.checked_text.clone().unwrap_or_else(|| self.clicked_text.clone())
+        }
+    }
+
+    pub fn background(&self, gui_state: &GuiState) -> Paint {
+        match gui_state {
+            GuiState::Base => self.base_background.clone(),
+            GuiState::Clicked => self.clicked_background.clone(),
+            GuiState::Selected => self.selected_background.clone(),
+            GuiState::Disabled => self.disabled_background.clone().unwrap_or_else(|| greyed(&self.base_background)),
+            GuiState::Checked => self.checked_background.clone().unwrap_or_else(|| self.clicked_background.clone())
+        }
+    }
+
+    // derive a full palette from a single accent color
+    pub fn from_accent(accent: Color) -> Palette {
+        let base_background = accent.darken(0.35);
+        let selected_background = accent.clone();
+        let clicked_background = accent.darken(0.15);
+        Palette::new(
+            accent.clone(),
+            base_background.contrasting_text(),
+            base_background,
+
+            accent.lighten(0.25),
+            selected_background.contrasting_text(),
+            selected_background,
+
+            accent.lighten(0.4),
+            clicked_background.contrasting_text(),
+            clicked_background
+        )
+    }
+}
+fn grey() -> Color {
+    Color::new(128, 128, 128)
+}
+
+fn greyed(paint: &Paint) -> Paint {
+    Paint::Solid(paint.base_color().mix(&grey(), 0.6))
 }
+
 // Gui Objects
 
 // abstract trait for keeping it Dry
@@ -110,6 +285,31 @@ pub trait Gui {
         false
     }
 
+    // x, y, w, h on the canvas, used to scroll the object into view
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        None
+    }
+
+    // every canvas layer group the object owns, for removing it from the canvas
+    fn layer_groups(&self) -> Vec<String> {
+        vec![]
+    }
+
+    // the config (theme) the object draws with
+    fn config(&self) -> Option<&'static GuiConfig> {
+        None
+    }
+
+    // swap the config (theme) and regenerate the layers
+    #[allow(unused_variables)]
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+    }
+
+    // swap the per widget style overrides and regenerate the layers
+    #[allow(unused_variables)]
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+    }
+
     #[allow(unused_variables)]
     fn set_text(&mut self,  text: String, canvas: &mut Canvas){
         ()
@@ -121,6 +321,29 @@ pub trait Gui {
     }
    
 
+    // numeric widgets, driven from a ViewStateUpdater
+    #[allow(unused_variables)]
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        None
+    }
+
+    // append a sample to one series of a chart
+    #[allow(unused_variables)]
+    fn push_value(&mut self, series: usize, value: f64, canvas: &mut Canvas) {
+    }
+
+    // boolean widgets, driven from a ViewStateUpdater
+    #[allow(unused_variables)]
+    fn set_checked(&mut self, checked: bool, canvas: &mut Canvas) {
+    }
+
+    fn is_checked(&self) -> Option<bool> {
+        None
+    }
+
     #[allow(unused_variables)]
     fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
         ()
@@ -129,32 +352,28 @@ pub trait Gui {
         GuiState::Base
     }
 
-    #[allow(unused_variables)]
-    fn handle_hid_event(&mut self, ba: &HIDEvent) -> (bool, Option<GuiState>, Option<Event>)  {
-        (true, None, None)
+    // the state to fall back to when the object is neither selected nor clicked
+    fn idle_gui_state(&self) -> GuiState {
+        GuiState::Base
     }
 
-}
+    // called every view update, for changes made while handling input
+    // returns true when the canvas needs to be rendered again
+    #[allow(unused_variables)]
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        false
+    }
 
-#[allow(dead_code)]
-pub struct Menu {
-    pub items: Vec<MenuItem>,
-    pub event: Event,
-    pub name: &'static str
-}
+    // drop an edit in progress as back would, true if there was one
+    fn cancel_edit(&mut self) -> bool {
+        false
+    }
 
-#[allow(dead_code)]
-pub struct MenuItem {
-    pub text: &'static str,
-    pub event: Event,
-    pub name: &'static str
-}
+    #[allow(unused_variables)]
+    fn handle_hid_event(&mut self, ba: &InputEvent) -> (bool, Option<GuiState>, Option<Event>)  {
+        (true, None, None)
+    }
 
-#[allow(dead_code)]
-pub struct GuiImage {
-    pub path: &'static str,
-    pub event: Event,
-    pub name: &'static str
 }
 
 
@@ -0,0 +1,251 @@
+use super::*;
+
+// ProgressBar
+// read only horizontal or vertical bar filled to value
+// vertical bars fill from the bottom up
+pub struct ProgressBar {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub orientation: Orientation,
+    pub precision: usize,
+    pub unit: &'static str,
+    pub show_value: bool,
+    pub name: String,
+    pub state_layers: StateLayers,
+    pub fill_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub gui_state: GuiState,
+    pub style: Style,
+    text: String,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl ProgressBar {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(value: f64, min: f64, max: f64, orientation: Orientation, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> ProgressBar {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let name = format!("ProgressBar - {}", uuid_string);
+        let fill_name = format!("{} - fill", name);
+        let state_layers = StateLayers::new(&name).with_live(&fill_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut progress_bar = ProgressBar {
+            value,
+            min: min.min(max),
+            max: max.max(min),
+            orientation,
+            precision: 0,
+            unit: "",
+            show_value: false,
+            name,
+            state_layers,
+            fill_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            text: String::new(),
+            dirty: false,
+            config
+        };
+        progress_bar.value = progress_bar.clamp(value);
+        progress_bar.text = progress_bar.format();
+        progress_bar.gen_layers();
+        progress_bar
+    }
+
+    // print the value over the bar, e.g. (0, "%")
+    pub fn with_format(mut self, precision: usize, unit: &'static str) -> ProgressBar {
+        self.precision = precision;
+        self.unit = unit;
+        self.show_value = true;
+        self.text = self.format();
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> ProgressBar {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    pub fn format(&self) -> String {
+        format!("{:.*}{}", self.precision, self.value, self.unit)
+    }
+
+    // 0.0 - 1.0 of the way from min to max
+    pub fn fraction(&self) -> f64 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    // frame: background and outline for each GuiState
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+        }
+    }
+
+    // the filled part and value text, regenerated whenever the value changes
+    fn gen_fill_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let inset = self.style.border_for().max(0);
+        let (fill_x, fill_y, fill_w, fill_h) = fill_area(
+            &self.orientation,
+            self.fraction(),
+            (self.x + inset, self.y + inset, self.w - 2 * inset, self.h - 2 * inset)
+        );
+        if fill_w > 0 && fill_h > 0 {
+            let fill = Rect::new(fill_x, fill_y, fill_w, fill_h, true, self.style.outline_for(&self.gui_state, self.config))
+                .with_radius((self.style.radius_for() - inset).max(0));
+            layers.push(Layer::new(Box::new(fill), self.state_layers.is_active(), self.fill_name.clone()));
+        }
+        if self.show_value {
+            let mut text = Text::new(
+                self.x,
+                self.y,
+                self.style.font_size_for(self.config.font_size),
+                self.text.clone(),
+                &self.config.font,
+                self.style.text_for(&self.gui_state, self.config),
+                self.style.padding_for(self.config)
+            );
+            if text.w < self.w {
+                text.x = self.x + (self.w - text.w) / 2;
+            }
+            if text.h < self.h {
+                text.y = self.y + (self.h - text.h) / 2;
+            }
+            layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.fill_name.clone()));
+        }
+        layers
+    }
+}
+
+// the part of area covered by fraction
+// horizontal fills left to right, vertical bottom to top
+pub fn fill_area(orientation: &Orientation, fraction: f64, area: (i32, i32, i32, i32)) -> (i32, i32, i32, i32) {
+    let (x, y, w, h) = area;
+    let fraction = fraction.clamp(0.0, 1.0);
+    match orientation {
+        Orientation::Horizontal => {
+            let fill_w = (w as f64 * fraction).round() as i32;
+            (x, y, fill_w, h)
+        },
+        Orientation::Vertical => {
+            let fill_h = (h as f64 * fraction).round() as i32;
+            (x, y + h - fill_h, w, fill_h)
+        }
+    }
+}
+
+impl Gui for ProgressBar {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_fill_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.text[..]
+    }
+
+    #[allow(unused_variables)]
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+        let value = self.clamp(value);
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.text = self.format();
+            self.dirty = true;
+        }
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        Some(self.value)
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.fill_name.clone(), self.gen_fill_layers());
+        true
+    }
+}
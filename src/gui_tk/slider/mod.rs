@@ -0,0 +1,346 @@
+use super::*;
+
+// Slider
+// interactive bar with a thumb
+// hat enters edit mode, left / right (or up / down when vertical) move the thumb,
+// hat again confirms and emits the event with the new value, back cancels
+pub struct Slider {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+    pub orientation: Orientation,
+    pub precision: usize,
+    pub event: Event,
+    pub name: &'static str,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    pub thumb_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub editing: bool,
+    pub gui_state: GuiState,
+    pub style: Style,
+    original: f64,
+    text: String,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl Slider {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(value: f64, min: f64, max: f64, step: f64, orientation: Orientation, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Slider {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("Slider - {}", uuid_string);
+        let thumb_name = format!("{} - thumb", layer_name);
+        let state_layers = StateLayers::new(&layer_name).with_live(&thumb_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut slider = Slider {
+            value,
+            min: min.min(max),
+            max: max.max(min),
+            step: valid_step(step, min.min(max), max.max(min)),
+            orientation,
+            precision: 0,
+            name: event.name,
+            event,
+            layer_name,
+            state_layers,
+            thumb_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            editing: false,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            original: value,
+            text: String::new(),
+            dirty: false,
+            config
+        };
+        slider.value = slider.snap(value);
+        slider.text = slider.format();
+        slider.gen_layers();
+        slider
+    }
+
+    // a zero or negative step is fixed up, see valid_step
+    pub fn with_step(mut self, step: f64) -> Slider {
+        self.step = valid_step(step, self.min, self.max);
+        self.value = self.snap(self.value);
+        self.text = self.format();
+        self
+    }
+
+    // decimal places of the value sent with the event
+    pub fn with_precision(mut self, precision: usize) -> Slider {
+        self.precision = precision;
+        self.text = self.format();
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Slider {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    pub fn format(&self) -> String {
+        format!("{:.*}", self.precision, self.value)
+    }
+
+    // clamp to min / max and round onto the step grid
+    fn snap(&self, value: f64) -> f64 {
+        snap(value, self.min, self.max, self.step)
+    }
+
+    pub fn fraction(&self) -> f64 {
+        if self.max > self.min {
+            (self.value - self.min) / (self.max - self.min)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn step_value(&mut self, direction: f64) {
+        let value = self.snap(self.value + direction * self.step);
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.text = self.format();
+            self.dirty = true;
+        }
+    }
+
+    // size of the thumb along the track
+    fn thumb_size(&self) -> i32 {
+        match self.orientation {
+            Orientation::Horizontal => (self.h / 2).max(4),
+            Orientation::Vertical => (self.w / 2).max(4)
+        }
+    }
+
+    // the track the thumb travels along, centered across the slider
+    fn track(&self) -> (i32, i32, i32, i32) {
+        let thumb = self.thumb_size();
+        match self.orientation {
+            Orientation::Horizontal => {
+                let track_h = (self.h / 4).max(2);
+                (self.x + thumb / 2, self.y + (self.h - track_h) / 2, self.w - thumb, track_h)
+            },
+            Orientation::Vertical => {
+                let track_w = (self.w / 4).max(2);
+                (self.x + (self.w - track_w) / 2, self.y + thumb / 2, track_w, self.h - thumb)
+            }
+        }
+    }
+
+    // track: background and outline for each GuiState
+    pub fn gen_layers(&mut self)  {
+        let (track_x, track_y, track_w, track_h) = self.track();
+        let radius = track_w.min(track_h) / 2;
+        for gui_state in GuiState::all().iter() {
+            self.layers.append(&mut self.state_layers.frame(gui_state, (track_x, track_y, track_w, track_h), radius, &self.style, self.config));
+        }
+    }
+
+    // filled track and thumb, regenerated whenever the value or state changes
+    fn gen_thumb_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let track = self.track();
+        let radius = track.2.min(track.3) / 2;
+        let color = self.style.outline_for(&self.gui_state, self.config);
+        let (fill_x, fill_y, fill_w, fill_h) = fill_area(&self.orientation, self.fraction(), track);
+        if fill_w > 0 && fill_h > 0 {
+            let fill = Rect::new(fill_x, fill_y, fill_w, fill_h, true, color.clone()).with_radius(radius);
+            layers.push(Layer::new(Box::new(fill), self.state_layers.is_active(), self.thumb_name.clone()));
+        }
+
+        // thumb centered on the end of the fill
+        let thumb = self.thumb_size();
+        let (thumb_x, thumb_y, thumb_w, thumb_h) = match self.orientation {
+            Orientation::Horizontal => (fill_x + fill_w - thumb / 2, self.y + (self.h - thumb * 2) / 2, thumb, thumb * 2),
+            Orientation::Vertical => (self.x + (self.w - thumb * 2) / 2, fill_y - thumb / 2, thumb * 2, thumb)
+        };
+        let thumb_layer = Rect::new(thumb_x, thumb_y, thumb_w, thumb_h, true, color).with_radius(self.style.radius_for());
+        layers.push(Layer::new(Box::new(thumb_layer), self.state_layers.is_active(), self.thumb_name.clone()));
+        if self.editing {
+            let outline = Rect::new(thumb_x, thumb_y, thumb_w, thumb_h, false, self.style.text_for(&self.gui_state, self.config))
+                .with_radius(self.style.radius_for());
+            layers.push(Layer::new(Box::new(outline), self.state_layers.is_active(), self.thumb_name.clone()));
+        }
+        layers
+    }
+
+    fn confirm(&mut self) -> Event {
+        self.editing = false;
+        self.dirty = true;
+        let mut event = self.event.clone();
+        event.values = Some(vec![self.text.clone()]);
+        event
+    }
+
+    fn cancel(&mut self) {
+        self.editing = false;
+        self.value = self.original;
+        self.text = self.format();
+        self.dirty = true;
+    }
+
+    // the direction a HID code moves the value in, if any
+    fn direction(&self, action: Action) -> Option<f64> {
+        match (&self.orientation, action) {
+            (_, Action::Left) => Some(-1.0),
+            (_, Action::Right) => Some(1.0),
+            (Orientation::Vertical, Action::Up) => Some(1.0),
+            (Orientation::Vertical, Action::Down) => Some(-1.0),
+            _ => None
+        }
+    }
+}
+
+impl Gui for Slider {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_thumb_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.text[..]
+    }
+
+    // store updates are ignored while the user is editing
+    #[allow(unused_variables)]
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+        if self.editing {
+            return;
+        }
+        let value = self.snap(value);
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.text = self.format();
+            self.dirty = true;
+        }
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        Some(self.value)
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.thumb_name.clone(), self.gen_thumb_layers());
+        true
+    }
+
+    fn cancel_edit(&mut self) -> bool {
+        if !self.editing {
+            return false;
+        }
+        self.cancel();
+        true
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match (self.editing, h_e.action, &h_e.io_state) {
+            // start editing
+            (false, Action::Select, IOState::Pressed) => {
+                self.editing = true;
+                self.original = self.value;
+                self.dirty = true;
+                (false, Some(GuiState::Clicked), None)
+            },
+            // release after confirming hands control back
+            (false, Action::Select, IOState::Released) => (true, Some(GuiState::Selected), None),
+            // confirm
+            (true, Action::Select, IOState::Pressed) => {
+                let event = self.confirm();
+                (false, Some(GuiState::Clicked), Some(event))
+            },
+            // back restores the value from before editing
+            (true, Action::Back, IOState::Pressed) => {
+                self.cancel();
+                (true, Some(GuiState::Selected), None)
+            },
+            (true, action, IOState::Pressed) | (true, action, IOState::Repeated) => {
+                if let Some(direction) = self.direction(action) {
+                    self.step_value(direction);
+                }
+                (false, None, None)
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
@@ -0,0 +1,348 @@
+use super::*;
+
+// held repeats before the step grows
+const ACCELERATION: [(u32, f64); 3] = [(0, 1.0), (5, 5.0), (15, 25.0)];
+
+// Spinner
+// numeric value editor
+// hat enters edit mode, up / down change the value,
+// hat again confirms and emits the event with the new value, back cancels
+pub struct Spinner {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+    pub precision: usize,
+    pub unit: &'static str,
+    pub formatter: Option<fn(f64) -> String>,
+    pub event: Event,
+    pub name: &'static str,
+    pub layer_name: String,
+    pub state_layers: StateLayers,
+    pub value_name: String,
+    // cloned and appended to canvas
+    pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub editing: bool,
+    pub gui_state: GuiState,
+    pub style: Style,
+    original: f64,
+    repeats: u32,
+    text: String,
+    dirty: bool,
+    config: &'static GuiConfig
+}
+
+#[allow(dead_code)]
+impl Spinner {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(value: f64, min: f64, max: f64, step: f64, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Spinner {
+        let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
+        let layer_name = format!("Spinner - {}", uuid_string);
+        let value_name = format!("{} - value", layer_name);
+        let state_layers = StateLayers::new(&layer_name).with_live(&value_name);
+        let layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let mut spinner = Spinner {
+            value,
+            min: min.min(max),
+            max: max.max(min),
+            step: valid_step(step, min.min(max), max.max(min)),
+            precision: 0,
+            unit: "",
+            formatter: None,
+            name: event.name,
+            event,
+            layer_name,
+            state_layers,
+            value_name,
+            layers,
+            x,
+            y,
+            w,
+            h,
+            editing: false,
+            gui_state: GuiState::Base,
+            style: Style::new(),
+            original: value,
+            repeats: 0,
+            text: String::new(),
+            dirty: false,
+            config
+        };
+        spinner.value = spinner.snap(value);
+        spinner.text = spinner.format();
+        spinner.gen_layers();
+        spinner
+    }
+
+    // a zero or negative step is fixed up, see valid_step
+    pub fn with_step(mut self, step: f64) -> Spinner {
+        self.step = valid_step(step, self.min, self.max);
+        self.value = self.snap(self.value);
+        self.text = self.format();
+        self
+    }
+
+    // decimal places and unit shown after the value, e.g. (1, "°C")
+    pub fn with_format(mut self, precision: usize, unit: &'static str) -> Spinner {
+        self.precision = precision;
+        self.unit = unit;
+        self.text = self.format();
+        self
+    }
+
+    // full control over the displayed and emitted text
+    pub fn with_formatter(mut self, formatter: fn(f64) -> String) -> Spinner {
+        self.formatter = Some(formatter);
+        self.text = self.format();
+        self
+    }
+
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> Spinner {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
+    pub fn reinit(&mut self, canvas: &mut Canvas){
+        self.state_layers.drop(canvas);
+
+        //gen new layers
+        self.gen_layers();
+        self.initialize(canvas);
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+    }
+
+    pub fn format(&self) -> String {
+        match self.formatter {
+            Some(formatter) => formatter(self.value),
+            None => format!("{:.*}{}", self.precision, self.value, self.unit)
+        }
+    }
+
+    // clamp to min / max and round onto the step grid
+    fn snap(&self, value: f64) -> f64 {
+        snap(value, self.min, self.max, self.step)
+    }
+
+    fn acceleration(&self) -> f64 {
+        ACCELERATION.iter()
+            .filter(|(repeats, _)| self.repeats >= *repeats)
+            .map(|(_, factor)| *factor)
+            .next_back()
+            .unwrap_or(1.0)
+    }
+
+    // move the value by direction steps, honouring held button acceleration
+    pub fn step_value(&mut self, direction: f64) {
+        let value = self.snap(self.value + direction * self.step * self.acceleration());
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.text = self.format();
+            self.dirty = true;
+        }
+    }
+
+    fn confirm(&mut self) -> Event {
+        self.editing = false;
+        self.dirty = true;
+        let mut event = self.event.clone();
+        event.values = Some(vec![self.text.clone()]);
+        event
+    }
+
+    fn cancel(&mut self) {
+        self.editing = false;
+        self.value = self.original;
+        self.text = self.format();
+        self.dirty = true;
+    }
+
+    // frame: background and outline for each GuiState
+    pub fn gen_layers(&mut self)  {
+        for gui_state in GuiState::all().iter() {
+            self.layers.append(&mut self.state_layers.frame(gui_state, (self.x, self.y, self.w, self.h), self.style.radius_for(), &self.style, self.config));
+        }
+    }
+
+    // the value text, regenerated whenever the value or state changes
+    fn gen_value_layers(&self) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let padding = self.style.padding_for(self.config);
+        let mut text = Text::new(
+            self.x,
+            self.y,
+            self.style.font_size_for(self.config.font_size),
+            self.text.clone(),
+            &self.config.font,
+            self.style.text_for(&self.gui_state, self.config),
+            padding
+        );
+        if text.w < self.w {
+            text.x = self.x + (self.w - text.w) / 2;
+        }
+        if text.h < self.h {
+            text.y = self.y + (self.h - text.h) / 2;
+        }
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        layers.push(Layer::new(Box::new(text), self.state_layers.is_active(), self.value_name.clone()));
+
+        // up / down markers while editing
+        if self.editing {
+            let marker_w = (self.h / 4).max(2);
+            let marker_x = self.x + self.w - marker_w - padding as i32 - 1;
+            let marker_color = self.style.outline_for(&self.gui_state, self.config);
+            if self.value < self.max {
+                let up = Rect::new(marker_x, self.y + padding as i32 + 1, marker_w, marker_w / 2, true, marker_color.clone());
+                layers.push(Layer::new(Box::new(up), self.state_layers.is_active(), self.value_name.clone()));
+            }
+            if self.value > self.min {
+                let down = Rect::new(marker_x, self.y + self.h - padding as i32 - 1 - marker_w / 2, marker_w, marker_w / 2, true, marker_color);
+                layers.push(Layer::new(Box::new(down), self.state_layers.is_active(), self.value_name.clone()));
+            }
+        }
+        layers
+    }
+}
+
+impl Gui for Spinner {
+    fn initialize(&mut self, canvas: &mut Canvas) -> bool {
+        // add all layers to the canvas
+        // self.layers is now empty
+        canvas.layers.append(&mut self.layers);
+        canvas.layers.append(&mut self.gen_value_layers());
+        true
+    }
+
+    fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.state_layers.activate(&self.gui_state, canvas);
+        true
+    }
+
+    fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
+        self.state_layers.deactivate(canvas);
+        true
+    }
+
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
+    fn get_text(&mut self) -> &str {
+        &self.text[..]
+    }
+
+    // store updates are ignored while the user is editing
+    #[allow(unused_variables)]
+    fn set_value(&mut self, value: f64, canvas: &mut Canvas) {
+        if self.editing {
+            return;
+        }
+        let value = self.snap(value);
+        if (value - self.value).abs() > f64::EPSILON {
+            self.value = value;
+            self.text = self.format();
+            self.dirty = true;
+        }
+    }
+
+    fn get_value(&self) -> Option<f64> {
+        Some(self.value)
+    }
+
+    fn set_gui_state(&mut self, gui_state: GuiState, canvas: &mut Canvas){
+        self.gui_state = gui_state;
+        if self.state_layers.is_active() {
+            self.activate(canvas);
+        }
+        self.dirty = true;
+    }
+
+    fn get_gui_state(&self) -> GuiState {
+        self.gui_state.clone()
+    }
+
+    fn refresh(&mut self, canvas: &mut Canvas) -> bool {
+        if !self.dirty {
+            return false;
+        }
+        self.dirty = false;
+        canvas.replace_layer_group(self.value_name.clone(), self.gen_value_layers());
+        true
+    }
+
+    fn cancel_edit(&mut self) -> bool {
+        if !self.editing {
+            return false;
+        }
+        self.cancel();
+        true
+    }
+
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match (self.editing, h_e.action, &h_e.io_state) {
+            // start editing
+            (false, Action::Select, IOState::Pressed) => {
+                self.editing = true;
+                self.original = self.value;
+                self.repeats = 0;
+                self.dirty = true;
+                (false, Some(GuiState::Clicked), None)
+            },
+            // release after confirming hands control back
+            (false, Action::Select, IOState::Released) => (true, Some(GuiState::Selected), None),
+            // confirm
+            (true, Action::Select, IOState::Pressed) => {
+                let event = self.confirm();
+                (false, Some(GuiState::Clicked), Some(event))
+            },
+            // back restores the value from before editing
+            (true, Action::Back, IOState::Pressed) => {
+                self.cancel();
+                (true, Some(GuiState::Selected), None)
+            },
+            (true, Action::Up, IOState::Pressed) | (true, Action::Down, IOState::Pressed) => {
+                self.repeats = 0;
+                self.step_value(if h_e.action == Action::Up { 1.0 } else { -1.0 });
+                (false, None, None)
+            },
+            (true, Action::Up, IOState::Repeated) | (true, Action::Down, IOState::Repeated) => {
+                self.repeats += 1;
+                self.step_value(if h_e.action == Action::Up { 1.0 } else { -1.0 });
+                (false, None, None)
+            },
+            (true, Action::Up, IOState::Released) | (true, Action::Down, IOState::Released) => {
+                self.repeats = 0;
+                (false, None, None)
+            },
+            _ => (false, None, None)
+        }
+
+        // true // returns back to view input handle
+        // false // keeps input mode here
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+// StateLayers
+// every gui object keeps one canvas layer group per GuiState
+// and shows exactly one of them at a time
+// new states only need to be added to GuiState::all()
+// live groups (a value, a knob) are redrawn by the widget and shown whatever the state
+#[derive(Clone, Debug)]
+pub struct StateLayers {
+    pub name: String,
+    groups: Vec<(GuiState, String)>,
+    live: Vec<String>,
+    active: bool
+}
+
+impl StateLayers {
+    pub fn new(name: &str) -> StateLayers {
+        let groups = GuiState::all().into_iter().map(|gui_state| {
+            let group = format!("{} - {}", name, gui_state.group_suffix());
+            (gui_state, group)
+        }).collect();
+        StateLayers {
+            name: name.to_string(),
+            groups,
+            live: vec![],
+            active: false
+        }
+    }
+
+    // a group the widget replaces on refresh, shown and dropped along with the state groups
+    pub fn with_live(mut self, group: &str) -> StateLayers {
+        self.live.push(group.to_string());
+        self
+    }
+
+    // shown since the last activate, live layers are generated visible or not to match
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    // the layer group name for gui_state
+    pub fn group(&self, gui_state: &GuiState) -> String {
+        match self.groups.iter().find(|(state, _)| state == gui_state) {
+            Some((_, group)) => group.clone(),
+            None => format!("{} - {}", self.name, gui_state.group_suffix())
+        }
+    }
+
+    pub fn groups(&self) -> Vec<String> {
+        self.groups.iter().map(|(_, group)| group.clone()).chain(self.live.iter().cloned()).collect()
+    }
+
+    // show the layers for gui_state and the live layers, hide the rest
+    pub fn activate(&mut self, gui_state: &GuiState, canvas: &mut Canvas) {
+        self.active = true;
+        for (state, group) in self.groups.iter() {
+            if state == gui_state {
+                canvas.activate_layer_group(group.clone());
+            } else {
+                canvas.deactivate_layer_group(group.clone());
+            }
+        }
+        for group in self.live.iter() {
+            canvas.activate_layer_group(group.clone());
+        }
+    }
+
+    pub fn deactivate(&mut self, canvas: &mut Canvas) {
+        self.active = false;
+        for group in self.groups() {
+            canvas.deactivate_layer_group(group);
+        }
+    }
+
+    pub fn drop(&self, canvas: &mut Canvas) {
+        for group in self.groups() {
+            canvas.drop_layer_group(group);
+        }
+    }
+
+    // the box most widgets sit in for gui_state: background, and an outline when the style has a border
+    pub fn frame(&self, gui_state: &GuiState, rect: (i32, i32, i32, i32), radius: i32, style: &Style, config: &GuiConfig) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let (x, y, w, h) = rect;
+        let group = self.group(gui_state);
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![
+            Layer::new(
+                Box::new(Rect::new(x, y, w, h, true, style.background_for(gui_state, config)).with_radius(radius)),
+                false,
+                group.clone()
+            )
+        ];
+        if style.border_for() > 0 {
+            layers.push(Layer::new(
+                Box::new(
+                    Rect::new(x, y, w, h, false, style.outline_for(gui_state, config))
+                        .with_radius(radius).with_border(style.border_for())
+                ),
+                false,
+                group
+            ));
+        }
+        layers
+    }
+
+    // pull all the layers back off the canvas, in state order
+    pub fn take(&self, canvas: &mut Canvas) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        for (_, group) in self.groups.iter() {
+            layers.append(&mut canvas.get_layer_group(group.clone()));
+        }
+        layers
+    }
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+// Stepped
+// value ranges moved a fixed step at a time, shared by Spinner and Slider
+
+// a step that can move a value across min..max
+// negative steps count as positive, zero or non finite ones would leave the value stuck
+// so they fall back to a hundredth of the range
+pub(super) fn valid_step(step: f64, min: f64, max: f64) -> f64 {
+    if step.is_finite() && step != 0.0 {
+        return step.abs();
+    }
+    warn!("Invalid step {}, using a hundredth of {}..{}", step, min, max);
+    let range = max - min;
+    if range.is_finite() && range > 0.0 {
+        range / 100.0
+    } else {
+        1.0
+    }
+}
+
+// clamp to min / max and round onto the step grid
+pub(super) fn snap(value: f64, min: f64, max: f64, step: f64) -> f64 {
+    let value = value.max(min).min(max);
+    if step > 0.0 {
+        let snapped = min + ((value - min) / step).round() * step;
+        snapped.max(min).min(max)
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_and_nan_steps_fall_back_to_the_range() {
+        assert_eq!(valid_step(0.0, 0.0, 50.0), 0.5);
+        assert_eq!(valid_step(f64::NAN, 0.0, 50.0), 0.5);
+        assert_eq!(valid_step(0.0, 5.0, 5.0), 1.0);
+    }
+
+    #[test]
+    fn negative_steps_count_as_positive() {
+        assert_eq!(valid_step(-2.0, 0.0, 10.0), 2.0);
+    }
+
+    #[test]
+    fn a_fallback_step_still_moves_the_value() {
+        let step = valid_step(0.0, 0.0, 10.0);
+        assert!(snap(5.0 + step, 0.0, 10.0, step) > 5.0);
+    }
+
+    #[test]
+    fn snap_rounds_onto_the_grid_and_clamps() {
+        assert_eq!(snap(7.4, 0.0, 10.0, 5.0), 5.0);
+        assert_eq!(snap(7.6, 0.0, 10.0, 5.0), 10.0);
+        assert_eq!(snap(12.0, 0.0, 10.0, 3.0), 9.0);
+        assert_eq!(snap(-1.0, 0.0, 10.0, 3.0), 0.0);
+        assert!(!snap(3.0, 0.0, 10.0, 0.0).is_nan());
+    }
+}
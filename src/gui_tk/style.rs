@@ -0,0 +1,104 @@
+use super::*;
+use std::collections::HashMap;
+
+// Style
+// per widget overrides, anything left as None falls back to the config
+// so a single red "Stop" button can sit next to the regular ones
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StateStyle {
+    pub outline: Option<Paint>,
+    pub text: Option<Color>,
+    pub background: Option<Paint>
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Style {
+    #[serde(default)]
+    pub states: HashMap<GuiState, StateStyle>,
+    pub border: Option<i32>,
+    pub radius: Option<i32>,
+    pub padding: Option<u32>,
+    pub font_size: Option<f32>
+}
+
+#[allow(dead_code)]
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    // builder style setters
+    pub fn outline(mut self, gui_state: GuiState, outline: impl Into<Paint>) -> Style {
+        self.states.entry(gui_state).or_default().outline = Some(outline.into());
+        self
+    }
+
+    pub fn text(mut self, gui_state: GuiState, text: Color) -> Style {
+        self.states.entry(gui_state).or_default().text = Some(text);
+        self
+    }
+
+    pub fn background(mut self, gui_state: GuiState, background: impl Into<Paint>) -> Style {
+        self.states.entry(gui_state).or_default().background = Some(background.into());
+        self
+    }
+
+    pub fn border(mut self, border: i32) -> Style {
+        self.border = Some(border);
+        self
+    }
+
+    pub fn radius(mut self, radius: i32) -> Style {
+        self.radius = Some(radius);
+        self
+    }
+
+    pub fn padding(mut self, padding: u32) -> Style {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn font_size(mut self, font_size: f32) -> Style {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    // resolve against the config
+    pub fn outline_for(&self, gui_state: &GuiState, config: &GuiConfig) -> Paint {
+        match self.states.get(gui_state).and_then(|s| s.outline.clone()) {
+            Some(outline) => outline,
+            None => config.palette.outline(gui_state)
+        }
+    }
+
+    pub fn text_for(&self, gui_state: &GuiState, config: &GuiConfig) -> Color {
+        match self.states.get(gui_state).and_then(|s| s.text.clone()) {
+            Some(text) => text,
+            None => config.palette.text(gui_state)
+        }
+    }
+
+    pub fn background_for(&self, gui_state: &GuiState, config: &GuiConfig) -> Paint {
+        match self.states.get(gui_state).and_then(|s| s.background.clone()) {
+            Some(background) => background,
+            None => config.palette.background(gui_state)
+        }
+    }
+
+    pub fn border_for(&self) -> i32 {
+        self.border.unwrap_or(1)
+    }
+
+    pub fn radius_for(&self) -> i32 {
+        self.radius.unwrap_or(0)
+    }
+
+    pub fn padding_for(&self, config: &GuiConfig) -> u32 {
+        self.padding.unwrap_or(config.padding)
+    }
+
+    pub fn font_size_for(&self, default: f32) -> f32 {
+        self.font_size.unwrap_or(default)
+    }
+}
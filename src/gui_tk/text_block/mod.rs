@@ -6,9 +6,7 @@ pub struct TextBlock {
     pub text: String,
     pub event: Event,
     pub name: String,
-    pub regular_name: String,
-    pub selected_name: String,
-    pub clicked_name: String,
+    pub state_layers: StateLayers,
     // cloned and appended to canvas
     pub layers: Vec<Layer<Box<dyn Draw + Send>>>,
     pub x: i32,
@@ -16,6 +14,7 @@ pub struct TextBlock {
     pub w: i32,
     pub h: i32,
     pub gui_state: GuiState,
+    pub style: Style,
     pub config: &'static GuiConfig
 }
 
@@ -24,10 +23,7 @@ impl TextBlock {
     pub fn new(text: String, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> TextBlock {
         let uuid_string = Uuid::new_v4().to_hyphenated().to_string();
         let name = format!("TextBlock - {}", uuid_string); 
-
-        let regular_name = format!("{} - regular", name);
-        let selected_name = format!("{} - selected", name);
-        let clicked_name = format!("{} - clicked", name);
+        let state_layers = StateLayers::new(&name);
 
         let gui_state =  GuiState::Base;
 
@@ -37,25 +33,22 @@ impl TextBlock {
             text,
             event,
             name,
-            regular_name,
-            clicked_name,
-            selected_name,
+            state_layers,
             layers,
             x,
             y,
             w,
             h,
             gui_state,
+            style: Style::new(),
             config
         };
         button.gen_layers();
         button
     }
     pub fn reinit(&mut self, canvas: &mut Canvas){
-        canvas.drop_layer_group(self.regular_name.clone());
-        canvas.drop_layer_group(self.selected_name.clone());
-        canvas.drop_layer_group(self.clicked_name.clone());
- 
+        self.state_layers.drop(canvas);
+
         //gen new layers
         self.gen_layers();
         self.initialize(canvas);
@@ -63,70 +56,48 @@ impl TextBlock {
 
     }
 
+    // regenerate the layers with per widget style overrides
+    pub fn with_style(mut self, style: Style) -> TextBlock {
+        self.style = style;
+        self.layers = vec![];
+        self.gen_layers();
+        self
+    }
+
     pub fn gen_layers(&mut self)  {
-        
-        // basic background box
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x, 
-                self.y, 
-                self.h as f32, 
-                self.text.clone(), 
-                &self.config.font,  
-                self.config.palette.base_text.clone(), 
-                2
-            )
-        );
-        let text_width = text.w;
-        if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
-        }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, self.regular_name.clone()); 
-
-        self.layers.push(text_layer);
-
-        // Clicked background box
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x, 
-                self.y, 
-                self.h as f32, 
-                self.text.clone(), 
-                &self.config.font,  
-                self.config.palette.clicked_text.clone(),
-                2
-            )
-        );
-        let text_width = text.w;
-        if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
-        }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, self.clicked_name.clone()); 
-
-        self.layers.push(text_layer);
-
-        // Selected background box
-        let mut text: Box<Text> = Box::new(
-            Text::new(
-                self.x, 
-                self.y, 
-                self.h as f32, 
-                self.text.clone(), 
-                &self.config.font,  
-                self.config.palette.selected_text.clone(), 
-                2
-            )
-        );
-        let text_width = text.w;
-        if text_width < self.w {
-            let x_offset = (self.w - text_width) / 2;
-            text.x = self.x + x_offset;
-        }
-        let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, self.selected_name.clone()); 
+        for gui_state in GuiState::all().iter() {
+            let group = self.state_layers.group(gui_state);
+            // text blocks only get a background when the style asks for one
+            if let Some(background) = self.style.states.get(gui_state).and_then(|s| s.background.clone()) {
+                let bg: Layer<Box<dyn Draw + Send>> = Layer::new(
+                    Box::new(
+                        Rect::new(self.x, self.y, self.w, self.h, true, background).with_radius(self.style.radius_for())
+                    ),
+                    false,
+                    group.clone()
+                );
+                self.layers.push(bg);
+            }
+            let mut text: Box<Text> = Box::new(
+                Text::new(
+                    self.x, 
+                    self.y, 
+                    self.style.font_size_for(self.config.font_size), 
+                    self.text.clone(), 
+                    &self.config.font,  
+                    self.style.text_for(gui_state, self.config), 
+                    self.style.padding_for(self.config)
+                )
+            );
+            let text_width = text.w;
+            if text_width < self.w {
+                let x_offset = (self.w - text_width) / 2;
+                text.x = self.x + x_offset;
+            }
+            let text_layer: Layer<Box<dyn Draw + Send>> = Layer::new(text, false, group.clone()); 
 
-        self.layers.push(text_layer);
+            self.layers.push(text_layer);
+        }
     }
 }
 impl Gui for TextBlock {
@@ -138,39 +109,42 @@ impl Gui for TextBlock {
     }
     fn activate(&mut self, canvas: &mut Canvas) -> bool {
         //set the correct layers to active        
-        match self.gui_state {
-            GuiState::Base => {
-                canvas.activate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-            },
-            GuiState::Clicked => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.deactivate_layer_group(self.selected_name.clone());
-                canvas.activate_layer_group(self.clicked_name.clone());
-            },
-            GuiState::Selected => {
-                canvas.deactivate_layer_group(self.regular_name.clone());
-                canvas.activate_layer_group(self.selected_name.clone());
-                canvas.deactivate_layer_group(self.clicked_name.clone());
-            }
-        };
+        self.state_layers.activate(&self.gui_state, canvas);
+        debug!("Activate {:?}: {}", self.gui_state, self.name);
         true
     }
 
     fn deactivate(&mut self, canvas: &mut Canvas) -> bool{
-        canvas.deactivate_layer_group(self.regular_name.clone());
-        canvas.deactivate_layer_group(self.selected_name.clone());
-        canvas.deactivate_layer_group(self.clicked_name.clone());
+        self.state_layers.deactivate(canvas);
         true 
     }
 
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        Some((self.x, self.y, self.w, self.h))
+    }
+
+    fn layer_groups(&self) -> Vec<String> {
+        self.state_layers.groups()
+    }
+
+    fn config(&self) -> Option<&'static GuiConfig> {
+        Some(self.config)
+    }
+
+    fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        self.config = config;
+        self.reinit(canvas);
+    }
+
+    fn set_style(&mut self, style: Style, canvas: &mut Canvas) {
+        self.style = style;
+        self.reinit(canvas);
+    }
+
     fn set_text(&mut self, text: String, canvas: &mut Canvas) {
         self.text = text;
-        // update regular_name
-        let mut layers = canvas.get_layer_group(self.regular_name.clone());
-        layers.append( &mut canvas.get_layer_group(self.selected_name.clone()));
-        layers.append( &mut canvas.get_layer_group(self.clicked_name.clone()));
+        // update every state's text layer
+        let mut layers = self.state_layers.take(canvas);
         // reverse here to preserve the order
         for layer in layers.iter_mut() {
             layer.item.update_text(self.text.clone())
@@ -191,9 +165,9 @@ impl Gui for TextBlock {
         self.gui_state.clone()
     }
 
-    fn handle_hid_event(&mut self, h_e: &HIDEvent) -> (bool, Option<GuiState>, Option<Event>) {
-        match h_e.code {
-            6 => {
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> (bool, Option<GuiState>, Option<Event>) {
+        match h_e.action {
+            Action::Select => {
                 match h_e.io_state {
                     IOState::Pressed => {
                             (false, Some(GuiState::Clicked), Some(self.event.clone()))
@@ -0,0 +1,54 @@
+use super::*;
+
+// Toggle
+// on / off switch, a rounded track with a sliding knob
+// hat flips it and emits the event with "true" or "false"
+pub type Toggle = Checked<Switch>;
+
+pub struct Switch;
+
+#[allow(dead_code)]
+impl Toggle {
+    pub fn new(checked: bool, x: i32, y: i32, w: i32, h: i32, event: Event, config: &'static GuiConfig) -> Toggle {
+        Checked::build(checked, String::new(), x, y, w, h, event, config)
+    }
+
+    fn radius(&self) -> i32 {
+        self.style.radius.unwrap_or(self.h / 2)
+    }
+}
+
+impl CheckedLook for Switch {
+    const KIND: &'static str = "Toggle";
+
+    // track: background and outline
+    fn gen_layers(toggle: &Toggle, gui_state: &GuiState) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        toggle.state_layers.frame(gui_state, (toggle.x, toggle.y, toggle.w, toggle.h), toggle.radius(), &toggle.style, toggle.config)
+    }
+
+    // filled track and knob
+    fn gen_mark_layers(toggle: &Toggle) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        let active = toggle.state_layers.is_active();
+        let inset = toggle.style.border_for().max(0) + 2;
+        let knob = (toggle.h - 2 * inset).max(2);
+        let radius = (toggle.radius() - inset).max(0);
+        if toggle.checked {
+            let fill = Rect::new(toggle.x, toggle.y, toggle.w, toggle.h, true, toggle.style.outline_for(&toggle.gui_state, toggle.config)).with_radius(toggle.radius());
+            layers.push(Layer::new(Box::new(fill), active, toggle.mark_name.clone()));
+        }
+        let knob_x = if toggle.checked {
+            toggle.x + toggle.w - inset - knob
+        } else {
+            toggle.x + inset
+        };
+        let knob_color = if toggle.checked {
+            toggle.style.background_for(&toggle.gui_state, toggle.config)
+        } else {
+            toggle.style.outline_for(&toggle.gui_state, toggle.config)
+        };
+        let knob = Rect::new(knob_x, toggle.y + inset, knob, knob, true, knob_color).with_radius(radius);
+        layers.push(Layer::new(Box::new(knob), active, toggle.mark_name.clone()));
+        layers
+    }
+}
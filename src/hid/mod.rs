@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::collections::HashMap;
 
 use rppal::gpio::{ Gpio, Level, InputPin};
 
@@ -7,6 +8,7 @@ use std::thread;
 
 use std::time::{Duration};
 use std::thread::JoinHandle;
+use log::*;
 
 pub fn run_button_pad(mut pad: ButtonPad) -> JoinHandle<()>{
     thread::spawn(move || {
@@ -51,12 +53,104 @@ pub struct HIDEvent {
     pub io_state: IOState,
     pub code: u8
 }
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum IOState {
     Pressed,
     Released,
     Repeated
 }
+
+// what a button means to the gui, whichever pin or code it comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Home,
+    Back,
+    Select,
+    Up,
+    Down,
+    Left,
+    Right,
+    // anything else, by code, for the app to handle
+    Custom(u8)
+}
+
+// an HIDEvent after the KeyMap, what views and widgets handle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InputEvent {
+    pub action: Action,
+    pub io_state: IOState
+}
+
+impl InputEvent {
+    pub fn new(action: Action, io_state: IOState) -> InputEvent {
+        InputEvent {action, io_state}
+    }
+}
+
+// KeyMap
+// HIDEvent codes to Actions, codes without an entry come through as Custom(code)
+// the default matches the standard pad: 0 home, 1 back, 2 left, 3 right, 4 up, 5 down, 6 hat
+//
+//  let key_map = KeyMap::from_keys(&button_initializers, &[
+//      ("push", Action::Select),
+//      ("cw", Action::Down),
+//      ("ccw", Action::Up)
+//  ]);
+#[derive(Clone, Debug)]
+pub struct KeyMap {
+    actions: HashMap<u8, Action>
+}
+
+impl Default for KeyMap {
+    fn default() -> KeyMap {
+        KeyMap::new()
+            .bind(0, Action::Home)
+            .bind(1, Action::Back)
+            .bind(2, Action::Left)
+            .bind(3, Action::Right)
+            .bind(4, Action::Up)
+            .bind(5, Action::Down)
+            .bind(6, Action::Select)
+    }
+}
+
+#[allow(dead_code)]
+impl KeyMap {
+    // nothing mapped, every code is Custom
+    pub fn new() -> KeyMap {
+        KeyMap {
+            actions: HashMap::new()
+        }
+    }
+
+    pub fn bind(mut self, code: u8, action: Action) -> KeyMap {
+        self.actions.insert(code, action);
+        self
+    }
+
+    // map by the keys given to the ButtonInitializers, keys not found are skipped
+    pub fn from_keys(initializers: &[ButtonInitializer], keys: &[(&str, Action)]) -> KeyMap {
+        let mut key_map = KeyMap::new();
+        for (key, action) in keys {
+            match initializers.iter().find(|initializer| initializer.key == *key) {
+                Some(initializer) => key_map = key_map.bind(initializer.code, *action),
+                None => warn!("No button with key {} to map to {:?}", key, action)
+            }
+        }
+        key_map
+    }
+
+    pub fn action(&self, code: u8) -> Action {
+        match self.actions.get(&code) {
+            Some(action) => *action,
+            None => Action::Custom(code)
+        }
+    }
+
+    pub fn map(&self, h_e: &HIDEvent) -> InputEvent {
+        InputEvent::new(self.action(h_e.code), h_e.io_state)
+    }
+}
 pub struct ButtonPad {
     buttons: Vec<Button>,
     pub button_sender: Sender<Vec<HIDEvent>>
@@ -167,3 +261,35 @@ pub mod helpers {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_map_matches_the_standard_pad() {
+        let key_map = KeyMap::default();
+        let actions: Vec<Action> = (0..7).map(|code| key_map.action(code)).collect();
+        assert_eq!(actions, vec![Action::Home, Action::Back, Action::Left, Action::Right, Action::Up, Action::Down, Action::Select]);
+    }
+
+    #[test]
+    fn custom_maps_follow_the_button_keys() {
+        let initializers = [
+            ButtonInitializer {pin: 17, code: 10, key: "push"},
+            ButtonInitializer {pin: 27, code: 11, key: "cw"}
+        ];
+        let key_map = KeyMap::from_keys(&initializers, &[("push", Action::Select), ("cw", Action::Down), ("ccw", Action::Up)]);
+        assert_eq!(key_map.action(10), Action::Select);
+        assert_eq!(key_map.action(11), Action::Down);
+        assert_eq!(key_map.map(&HIDEvent {io_state: IOState::Repeated, code: 11}), InputEvent::new(Action::Down, IOState::Repeated));
+        // nothing from the defaults carries over
+        assert_eq!(key_map.action(0), Action::Custom(0));
+    }
+
+    #[test]
+    fn unmapped_codes_are_custom() {
+        assert_eq!(KeyMap::default().action(42), Action::Custom(42));
+        assert_eq!(KeyMap::new().bind(42, Action::Back).action(42), Action::Back);
+    }
+}
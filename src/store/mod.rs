@@ -32,7 +32,9 @@ pub struct Store {
     pub filtered_state_senders: Vec<FilteredStateSender>,
     pub action_receiver: Receiver<Action>,
     action_sender: Sender<Action>,   
-    pub reducers: HashMap<&'static str, Reducer>
+    pub reducers: HashMap<&'static str, Reducer>,
+    // actions copied on to another thread as they come in, e.g. WindowViewer navigation
+    action_forwarders: HashMap<&'static str, Vec<Sender<Action>>>
 }
 
 impl Store {
@@ -44,7 +46,8 @@ impl Store {
             state,
             filtered_state_senders: vec![],
             action_receiver: receiver,
-            action_sender: sender       
+            action_sender: sender,
+            action_forwarders: HashMap::new()
             
         }
     }
@@ -58,13 +61,21 @@ impl Store {
     }
 
 
+    // sender gets a copy of every action named name, before it is reduced
+    pub fn reg_action_forwarder(&mut self, name: &'static str, sender: Sender<Action>) {
+        self.action_forwarders.entry(name).or_default().push(sender);
+    }
+
     pub fn get_action_sender(&self) -> Sender<Action> {
         self.action_sender.clone()
     }
 
 
     pub fn reduce(&mut self, action: Action) -> bool{
-        //
+        if let Some(senders) = self.action_forwarders.get_mut(action.name) {
+            // drop the ones whose receiver has gone
+            senders.retain(|sender| sender.send(action.clone()).is_ok());
+        }
         let reduced = match self.reducers.get(action.name) {
             Some(reducer_fn) =>  {
                 let reducer: Reducer = *reducer_fn;
@@ -89,6 +100,7 @@ impl Store {
 }
 
 #[allow(dead_code)]
+#[derive(Clone, Debug)]
 pub struct Action {
     pub name: &'static str,
     pub values: Option<Vec<String>>
@@ -105,45 +117,10 @@ impl Action {
 }
 
 pub fn eq_gui_states(gui_states_1: &Vec<GuiState>, gui_states_2: &Vec<GuiState>) -> bool {
-    if gui_states_1.len() == gui_states_2.len() {
-        let mut eq = true;
-        for i in 0..gui_states_1.len() {
-            if !eq_gui_state(&gui_states_1[i], &gui_states_2[i]) {
-                eq = false;
-                break;        
-            }
-        }
-        eq
-    } else {
-        false
-    } 
+    gui_states_1 == gui_states_2
 }
 
 
 pub fn eq_gui_state(gui_state_1: &GuiState, gui_state_2: &GuiState) -> bool {
-    let mut eq = true;
-    if let GuiState::Base = gui_state_1 {
-        eq = match gui_state_2 {
-            GuiState::Base => true,
-            _ => false
-        };
-    }
-
-    if let GuiState::Clicked = gui_state_1 {
-        eq = match gui_state_2 {
-            GuiState::Clicked => true,
-            _ => false
-        };
-    }
-
-    if let GuiState::Selected = gui_state_1 {
-        eq = match gui_state_2 {
-            GuiState::Selected => true,
-            _ => false
-        };
-    }
-    return eq
+    gui_state_1 == gui_state_2
 }
-
-
-
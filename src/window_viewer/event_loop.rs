@@ -0,0 +1,162 @@
+use super::*;
+use std::mem;
+use std::time::Instant;
+
+pub const DEFAULT_FPS: u32 = 30;
+
+// why the loop woke up, timer ticks are timeouts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wake {
+    Input,
+    State,
+    Navigation,
+    Tick
+}
+
+// Event Loop
+// run_window_viewer sleeps until input, a state update or a navigation Action arrives,
+// or a toast is due to expire, every receiver is relayed through a thread that wakes it
+// (view state only while the view is shown)
+// while anything is still changing (e.g. a gauge needle settling) it steps once per frame
+// renders are coalesced to at most fps a second
+//
+//  window_viewer.set_frame_rate(20);
+//  run_window_viewer(window_viewer);
+// map_or rather than is_none_or, which needs rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+pub fn run_window_viewer(mut window_viewer: WindowViewer) -> JoinHandle<()>{
+    window_viewer.activate_bar();
+    thread::spawn(move || {
+        let wake = window_viewer.wake_source();
+        window_viewer.enter_active();
+        let frame = window_viewer.frame_time();
+        // changes not rendered yet, and whether the last step changed anything
+        let mut pending = false;
+        let mut busy = true;
+        let mut last_render: Option<Instant> = None;
+        loop {
+            let now = Instant::now();
+            let deadline = if pending || busy {
+                Some(last_render.map_or(now, |rendered| rendered + frame).max(now))
+            } else {
+                window_viewer.next_deadline()
+            };
+            // the WindowViewer holds a sender, so the channel never disconnects
+            let woken = match deadline {
+                Some(deadline) => wake.recv_timeout(deadline.saturating_duration_since(now)).ok(),
+                None => wake.recv().ok()
+            };
+            debug!("Woken by {:?}", woken.unwrap_or(Wake::Tick));
+            busy = window_viewer.step();
+            pending |= busy;
+            if pending && last_render.map_or(true, |rendered| rendered.elapsed() >= frame) {
+                info!("about to render {}",  window_viewer.active);
+                window_viewer.render();
+                last_render = Some(Instant::now());
+                pending = false;
+            }
+        }
+    })
+}
+
+// hand items on from receiver to a new one, waking the loop for each
+fn relay<T: Send + 'static>(receiver: &mut Receiver<T>, wake: &Sender<Wake>, reason: Wake) {
+    relay_while(receiver, wake, reason, Arc::new(AtomicBool::new(true)));
+}
+
+// relay, but only wake the loop while awake is set, items still queue up for later
+fn relay_while<T: Send + 'static>(receiver: &mut Receiver<T>, wake: &Sender<Wake>, reason: Wake, awake: Arc<AtomicBool>) {
+    let (relay_sender, relay_receiver) = channel();
+    let source = mem::replace(receiver, relay_receiver);
+    let wake = wake.clone();
+    thread::spawn(move || {
+        while let Ok(item) = source.recv() {
+            if relay_sender.send(item).is_err() {
+                break;
+            }
+            if awake.load(Ordering::Relaxed) && wake.send(reason).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[allow(dead_code)]
+impl WindowViewer {
+    // cap on renders a second
+    pub fn set_frame_rate(&mut self, fps: u32) {
+        self.fps = fps.max(1);
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+
+    // one pass over input and updates, true if anything needs rendering
+    pub fn step(&mut self) -> bool {
+        while let Ok(hid_events) = self.input_receiver.try_recv() {
+            for h_e in &hid_events {
+                debug!("HIDEvent: {:#?}", h_e);
+                if let Some(event) = self.handle_hid_event(h_e) {
+                    self.handle_event(event);
+                }
+            }
+        }
+        let bar_update = self.update_bar();
+        let active_update = self.update_active_view();
+        let navigated = self.update_navigation();
+        let view_change = self.window_viewer_updater() || navigated;
+        let toast_update = self.update_toasts(bar_update || active_update || view_change);
+        bar_update || active_update || view_change || toast_update
+    }
+
+    // when the loop has to wake without being sent anything
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.toasts.next_deadline()
+    }
+
+    // relay every receiver the loop reads into one channel to block on
+    // views and modals added later are relayed as they come in
+    pub fn wake_source(&mut self) -> Receiver<Wake> {
+        let (wake, wake_receiver) = channel();
+        relay(&mut self.input_receiver, &wake, Wake::Input);
+        relay(&mut self.state_receiver, &wake, Wake::State);
+        relay(&mut self.navigation_receiver, &wake, Wake::Navigation);
+        self.bar.relay_state(&wake);
+        for view in self.views.iter_mut() {
+            view.relay_state(&wake);
+        }
+        for modal in self.modals.iter_mut() {
+            modal.view.relay_state(&wake);
+        }
+        self.wake = Some(wake);
+        wake_receiver
+    }
+}
+
+impl View {
+    // state sent while the view is hidden waits, without waking the loop, until it is shown
+    pub(super) fn relay_state(&mut self, wake: &Sender<Wake>) {
+        relay_while(&mut self.state_receiver, wake, Wake::State, self.shown.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_views_get_their_state_without_waking_the_loop() {
+        let (wake, wake_receiver) = channel();
+        let (sender, mut receiver) = channel();
+        let shown = Arc::new(AtomicBool::new(false));
+        relay_while(&mut receiver, &wake, Wake::State, shown.clone());
+        sender.send(1).unwrap();
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(1)), Ok(1));
+        assert!(wake_receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        shown.store(true, Ordering::Relaxed);
+        sender.send(2).unwrap();
+        assert_eq!(wake_receiver.recv_timeout(Duration::from_secs(1)), Ok(Wake::State));
+        assert_eq!(receiver.try_recv(), Ok(2));
+    }
+}
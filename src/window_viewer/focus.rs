@@ -0,0 +1,257 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+impl Direction {
+    pub fn from_delta(dx: isize, dy: isize) -> Option<Direction> {
+        match (dx.signum(), dy.signum()) {
+            (1, _) => Some(Direction::Right),
+            (-1, _) => Some(Direction::Left),
+            (_, 1) => Some(Direction::Down),
+            (_, -1) => Some(Direction::Up),
+            _ => None
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left
+        }
+    }
+}
+
+// Focus
+// wrap-around and hand made traversal on top of grid or spatial navigation
+//
+//  let view = View::new(updater, receiver).with_wrap(false, true);
+//  view.set_focus(ok, Direction::Right, cancel);
+//  view.set_focus_chain(&[name, age, save], Direction::Down, true);
+#[allow(dead_code)]
+impl View {
+    // carry on from the other end when moving past the last row / column
+    pub fn with_wrap(mut self, horizontal: bool, vertical: bool) -> View {
+        self.set_wrap(horizontal, vertical);
+        self
+    }
+
+    pub fn set_wrap(&mut self, horizontal: bool, vertical: bool) {
+        self.wrap = (horizontal, vertical);
+    }
+
+    // pressing direction on from selects to, whatever the grid or geometry says
+    pub fn set_focus(&mut self, from: usize, direction: Direction, to: usize) {
+        if from >= self.objects.len() || to >= self.objects.len() {
+            debug!("Cannot set focus between non existent objects {} and {}", from, to);
+            return;
+        }
+        self.focus_overrides.retain(|(object, way, _)| !(*object == from && *way == direction));
+        self.focus_overrides.push((from, direction, to));
+    }
+
+    pub fn clear_focus(&mut self, from: usize, direction: Direction) {
+        self.focus_overrides.retain(|(object, way, _)| !(*object == from && *way == direction));
+    }
+
+    // objects visited in order moving in direction, and back again the opposite way
+    // looped joins the last one back to the first
+    pub fn set_focus_chain(&mut self, objects: &[usize], direction: Direction, looped: bool) {
+        for pair in objects.windows(2) {
+            self.set_focus(pair[0], direction, pair[1]);
+            self.set_focus(pair[1], direction.opposite(), pair[0]);
+        }
+        if let (true, Some(first), Some(last)) = (looped, objects.first(), objects.last()) {
+            if first != last {
+                self.set_focus(*last, direction, *first);
+                self.set_focus(*first, direction.opposite(), *last);
+            }
+        }
+    }
+
+    // navigable, enabled objects in nav_index order
+    pub fn navigable_objects(&self) -> Vec<usize> {
+        let mut objects = vec![];
+        for (r_index, row) in self.nav_index.iter().enumerate() {
+            for c_index in 0..row.len() {
+                objects.append(&mut self.cell(r_index, c_index));
+            }
+        }
+        objects
+    }
+
+    // move the selection straight to an object, false if it can't be selected
+    pub fn select_object(&mut self, object: usize) -> bool {
+        let (row, column) = match self.find_object_cell(object) {
+            Some(cell) => cell,
+            None => return false
+        };
+        if !self.gui_state[object].is_enabled() {
+            return false;
+        }
+        let changed = self.selected_object != object;
+        self.selected_object = object;
+        self.selected_row = row;
+        self.selected_column = column;
+        if changed {
+            self.move_selection();
+        }
+        changed
+    }
+
+    pub(super) fn focus_override(&mut self, dx: isize, dy: isize) -> bool {
+        let direction = match Direction::from_delta(dx, dy) {
+            Some(direction) => direction,
+            None => return false
+        };
+        let target = self.focus_overrides.iter()
+            .find(|(object, way, _)| *object == self.selected_object && *way == direction)
+            .map(|(_, _, to)| *to);
+        match target {
+            Some(to) => self.select_object(to),
+            None => false
+        }
+    }
+
+    // grid wrap, the first selectable cell from the far end of the row or column
+    pub(super) fn grid_wrap(&mut self, dx: isize, dy: isize) -> bool {
+        if dx != 0 {
+            let row = self.selected_row;
+            let columns = self.nav_index.get(row).map_or(0, |cells| cells.len());
+            let mut order: Vec<usize> = (0..columns).collect();
+            if dx < 0 {
+                order.reverse();
+            }
+            for column in order {
+                let cell = self.cell(row, column);
+                let object = if dx > 0 { cell.first() } else { cell.last() };
+                if let Some(object) = object {
+                    return self.select_object(*object);
+                }
+            }
+        } else if dy != 0 {
+            let mut order: Vec<usize> = (0..self.nav_index.len()).collect();
+            if dy < 0 {
+                order.reverse();
+            }
+            for row in order {
+                if let Some(column) = self.closest_column(row, self.selected_column) {
+                    return self.select_object(self.cell(row, column)[0]);
+                }
+            }
+        }
+        false
+    }
+
+    pub(super) fn reindex_focus(&mut self, removed: usize) {
+        self.focus_overrides.retain(|(from, _, to)| *from != removed && *to != removed);
+        for (from, _, to) in self.focus_overrides.iter_mut() {
+            if *from > removed {
+                *from -= 1;
+            }
+            if *to > removed {
+                *to -= 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::*;
+
+    fn press(view: &mut View, action: Action) -> usize {
+        view.nav(&InputEvent::new(action, IOState::Pressed));
+        view.selected_object
+    }
+
+    #[test]
+    fn chains_link_both_ways() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        view.set_focus_chain(&[0, 2, 1], Direction::Down, false);
+        assert_eq!(press(&mut view, Action::Down), 2);
+        assert_eq!(press(&mut view, Action::Down), 1);
+        assert_eq!(press(&mut view, Action::Up), 2);
+        assert_eq!(press(&mut view, Action::Up), 0);
+    }
+
+    #[test]
+    fn looped_chains_join_the_ends() {
+        let mut view = grid_view(&[(0, 0), (1, 0), (2, 0)]);
+        view.set_focus_chain(&[0, 1, 2], Direction::Right, true);
+        view.select_object(2);
+        assert_eq!(press(&mut view, Action::Right), 0);
+        assert_eq!(press(&mut view, Action::Left), 2);
+    }
+
+    #[test]
+    fn open_chains_fall_back_to_the_grid_at_the_ends() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (1, 0)]);
+        view.set_focus_chain(&[0, 1], Direction::Right, false);
+        assert_eq!(press(&mut view, Action::Left), 0);
+        assert_eq!(press(&mut view, Action::Down), 2);
+    }
+
+    #[test]
+    fn focus_skips_disabled_targets() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        view.set_focus(0, Direction::Right, 2);
+        view.gui_state[2] = GuiState::Disabled;
+        assert_eq!(press(&mut view, Action::Right), 1);
+    }
+
+    #[test]
+    fn no_wrap_stops_at_the_edges() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(press(&mut view, Action::Left), 0);
+        assert_eq!(press(&mut view, Action::Up), 0);
+        view.select_object(3);
+        assert_eq!(press(&mut view, Action::Right), 3);
+        assert_eq!(press(&mut view, Action::Down), 3);
+    }
+
+    #[test]
+    fn wrap_carries_on_from_the_other_end() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2), (1, 0), (2, 0)]).with_wrap(true, true);
+        assert_eq!(press(&mut view, Action::Left), 2);
+        assert_eq!(press(&mut view, Action::Right), 0);
+        assert_eq!(press(&mut view, Action::Up), 4);
+        assert_eq!(press(&mut view, Action::Down), 0);
+    }
+
+    #[test]
+    fn wrap_is_per_axis() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (1, 0)]).with_wrap(true, false);
+        assert_eq!(press(&mut view, Action::Up), 0);
+        assert_eq!(press(&mut view, Action::Left), 1);
+    }
+
+    #[test]
+    fn reindexing_drops_and_shifts_overrides() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2), (0, 3)]);
+        view.set_focus(0, Direction::Down, 1);
+        view.set_focus(1, Direction::Down, 3);
+        view.set_focus(3, Direction::Up, 2);
+        view.reindex_focus(1);
+        assert_eq!(view.focus_overrides, vec![(2, Direction::Up, 1)]);
+    }
+
+    #[test]
+    fn setting_focus_again_replaces_it() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        view.set_focus(0, Direction::Down, 1);
+        view.set_focus(0, Direction::Down, 2);
+        view.set_focus(0, Direction::Down, 9);
+        assert_eq!(view.focus_overrides, vec![(0, Direction::Down, 2)]);
+        view.clear_focus(0, Direction::Down);
+        assert!(view.focus_overrides.is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+use super::*;
+
+// runs as a view is entered or left, the event returned is dispatched
+pub type ViewHook = fn(&mut View) -> Option<Event>;
+
+// History
+// views pushed with push_view are stacked, back (or pop_view) returns to the one before
+// with the object that was selected when it was left, home clears the stack
+//
+//  let settings = View::new(updater, receiver)
+//      .with_on_enter(|_view| Some(Event::new("Load Settings", None)))
+//      .with_escape_event(Event::new("Settings Closed", None));
+//  window_viewer.push_view(SETTINGS);
+#[allow(dead_code)]
+impl WindowViewer {
+    // go to a view, back returns to the one active now
+    pub fn push_view(&mut self, view: usize) -> bool {
+        if self.views.len() <= view {
+            debug!("Cannot push view {} which does not exist", view);
+            return false;
+        }
+        if view != self.active {
+            self.history.push(self.active);
+        }
+        self.switch_view(view, false);
+        true
+    }
+
+    // back to the previous view, selection restored, false when there is none
+    pub fn pop_view(&mut self) -> bool {
+        match self.history.pop() {
+            Some(view) => {
+                self.switch_view(view, true);
+                true
+            },
+            None => false
+        }
+    }
+
+    // the root view, forgetting the history
+    pub fn go_home(&mut self) {
+        self.history.clear();
+        if !self.views.is_empty() {
+            self.switch_view(0, false);
+        }
+    }
+
+    // views back will return to, most recent last
+    pub fn history(&self) -> &[usize] {
+        &self.history[..]
+    }
+
+    // leave the active view for another one, running their hooks
+    // resume keeps the selection the view had when it was left
+    // already on the view, nothing happens
+    pub(super) fn switch_view(&mut self, view: usize, resume: bool) {
+        if view == self.active && self.views[view].entered {
+            return;
+        }
+        let mut events = vec![];
+        if self.active != view && self.active < self.views.len() {
+            let leaving = &mut self.views[self.active];
+            events.extend(leaving.leave());
+        }
+        self.show_view(view, resume);
+        events.extend(self.views[view].enter());
+        for event in events {
+            self.handle_event(event);
+        }
+    }
+
+    // the view showing when the loop starts has not been switched to, run its on_enter
+    pub(super) fn enter_active(&mut self) {
+        let entered = match self.views.get_mut(self.active) {
+            Some(view) => view.enter(),
+            None => None
+        };
+        if let Some(event) = entered {
+            self.handle_event(event);
+        }
+    }
+
+    // activate one view and deactivate the rest, no hooks
+    pub(super) fn show_view(&mut self, view: usize, resume: bool) {
+        for i in 0..self.views.len() {
+            if i == view {
+                self.active = view;
+                info!("activating {}", self.active);
+                if resume {
+                    self.views[self.active].resume(&mut self.canvas);
+                } else {
+                    self.views[self.active].activate(&mut self.canvas);
+                }
+            } else {
+                info!("deactivating {}", i);
+                self.views[i].deactivate(&mut self.canvas);
+            }
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    pub fn with_on_enter(mut self, on_enter: ViewHook) -> View {
+        self.on_enter = Some(on_enter);
+        self
+    }
+
+    pub fn with_on_leave(mut self, on_leave: ViewHook) -> View {
+        self.on_leave = Some(on_leave);
+        self
+    }
+
+    // sent when back leaves the view
+    pub fn with_escape_event(mut self, escape_event: Event) -> View {
+        self.escape_event = Some(escape_event);
+        self
+    }
+
+    pub fn is_navigating(&self) -> bool {
+        matches!(self.input_mode, InputMode::Navigate)
+    }
+
+    // a widget left mid edit drops it, the view goes back to navigating
+    pub(super) fn cancel_edit(&mut self) {
+        self.input_mode = InputMode::Navigate;
+        if self.selected_object < self.objects.len() && self.objects[self.selected_object].cancel_edit() {
+            self.gui_state[self.selected_object] = GuiState::Selected;
+            self.stale = true;
+        }
+    }
+
+    // the hooks only run once per visit, however often the view is shown again
+    fn enter(&mut self) -> Option<Event> {
+        if self.entered {
+            return None;
+        }
+        self.entered = true;
+        let on_enter = self.on_enter?;
+        on_enter(self)
+    }
+
+    fn leave(&mut self) -> Option<Event> {
+        if !self.entered {
+            return None;
+        }
+        self.entered = false;
+        let on_leave = self.on_leave?;
+        on_leave(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util;
+    use super::super::super::gui_tk::Spinner;
+    use std::sync::mpsc::channel;
+
+    fn view() -> View {
+        let (_, receiver) = channel();
+        View::new(|_, _, _| (), receiver).with_escape_event(Event::new("Escaped", None))
+    }
+
+    #[test]
+    fn back_press_and_release_escape_once() {
+        let mut view = view();
+        let escapes = [IOState::Pressed, IOState::Released, IOState::Repeated].iter()
+            .filter_map(|io_state| view.handle_hid_event(&InputEvent::new(Action::Back, *io_state)))
+            .count();
+        assert_eq!(escapes, 1);
+    }
+
+    #[test]
+    fn hooks_run_once_per_visit() {
+        let mut view = view()
+            .with_on_enter(|_| Some(Event::new("Entered", None)))
+            .with_on_leave(|_| Some(Event::new("Left", None)));
+        assert!(view.leave().is_none());
+        assert!(view.enter().is_some());
+        assert!(view.enter().is_none());
+        assert!(view.leave().is_some());
+        assert!(view.leave().is_none());
+    }
+
+    #[test]
+    fn back_release_alone_does_not_escape() {
+        let mut view = view();
+        assert!(view.handle_hid_event(&InputEvent::new(Action::Back, IOState::Released)).is_none());
+    }
+
+    #[test]
+    fn leaving_mid_edit_cancels_the_edit() {
+        let mut view = view();
+        let spinner = Spinner::new(5.0, 0.0, 10.0, 1.0, 0, 0, 100, 40, Event::new("Set", None), test_util::config());
+        view.add_object(Box::new(spinner), 0, 0);
+        view.handle_hid_event(&InputEvent::new(Action::Select, IOState::Pressed));
+        view.handle_hid_event(&InputEvent::new(Action::Up, IOState::Pressed));
+        assert!(!view.is_navigating());
+        assert_eq!(view.objects[0].get_value(), Some(6.0));
+        // what home or a view switch does to the view being left
+        view.cancel_edit();
+        assert!(view.is_navigating());
+        assert_eq!(view.objects[0].get_value(), Some(5.0));
+        // select starts a new edit rather than confirming the old one
+        assert!(view.handle_hid_event(&InputEvent::new(Action::Select, IOState::Pressed)).is_none());
+    }
+}
@@ -0,0 +1,428 @@
+use super::*;
+use serde::{Serialize, Deserialize};
+
+// Layout
+// containers that work out widget geometry instead of hand placed x, y, w, h
+// widgets are built by factories once their bounds are known,
+// navigable ones are registered in the view's nav_index in layout order
+//
+//  let layout = Layout::vstack().padding(4).spacing(4)
+//      .widget(|x, y, w, h| Box::new(Button::new(...)))
+//      .nested(Layout::hstack().widget(...).widget(...));
+//  view.add_layout(layout, 0, 0, 320, 240);
+
+// builds a widget once the layout knows where it goes
+pub type WidgetFactory = Box<dyn FnOnce(i32, i32, i32, i32) -> Box<dyn Gui + Send>>;
+
+// how much of the stack direction a child takes
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Size {
+    Fixed(i32),
+    // share of the space left over after the fixed children
+    Fill(u32)
+}
+
+// where a child with a cross size sits across the stack direction
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Align {
+    Start,
+    Center,
+    End
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutKind {
+    HStack,
+    VStack,
+    Grid(usize)
+}
+
+enum Content {
+    Widget(WidgetFactory),
+    Layout(Layout)
+}
+
+pub struct LayoutChild {
+    content: Content,
+    size: Size,
+    // None stretches across the stack
+    cross_size: Option<i32>,
+    navigable: bool,
+    // state key the view's binder feeds the widget from
+    bind: Option<&'static str>
+}
+
+#[allow(dead_code)]
+impl LayoutChild {
+    pub fn widget(factory: impl FnOnce(i32, i32, i32, i32) -> Box<dyn Gui + Send> + 'static) -> LayoutChild {
+        LayoutChild {
+            content: Content::Widget(Box::new(factory)),
+            size: Size::Fill(1),
+            cross_size: None,
+            navigable: true,
+            bind: None
+        }
+    }
+
+    // labels, frames and other objects the selection skips
+    pub fn static_widget(factory: impl FnOnce(i32, i32, i32, i32) -> Box<dyn Gui + Send> + 'static) -> LayoutChild {
+        LayoutChild {
+            navigable: false,
+            ..LayoutChild::widget(factory)
+        }
+    }
+
+    pub fn layout(layout: Layout) -> LayoutChild {
+        LayoutChild {
+            content: Content::Layout(layout),
+            size: Size::Fill(1),
+            cross_size: None,
+            navigable: true,
+            bind: None
+        }
+    }
+
+    pub fn size(mut self, size: Size) -> LayoutChild {
+        self.size = size;
+        self
+    }
+
+    pub fn cross_size(mut self, cross_size: i32) -> LayoutChild {
+        self.cross_size = Some(cross_size);
+        self
+    }
+
+    pub fn bind(mut self, key: &'static str) -> LayoutChild {
+        self.bind = Some(key);
+        self
+    }
+
+    // rows and columns of nav_index the child takes up
+    fn extent(&self) -> (usize, usize) {
+        match &self.content {
+            Content::Widget(_) => if self.navigable { (1, 1) } else { (0, 0) },
+            Content::Layout(layout) => layout.extent()
+        }
+    }
+}
+
+pub struct Layout {
+    pub kind: LayoutKind,
+    pub spacing: i32,
+    pub padding: i32,
+    pub align: Align,
+    children: Vec<LayoutChild>
+}
+
+#[allow(dead_code)]
+impl Layout {
+    pub fn new(kind: LayoutKind) -> Layout {
+        Layout {
+            kind,
+            spacing: 0,
+            padding: 0,
+            align: Align::Center,
+            children: vec![]
+        }
+    }
+
+    // children left to right, each in the next nav_index column
+    pub fn hstack() -> Layout {
+        Layout::new(LayoutKind::HStack)
+    }
+
+    // children top to bottom, each in the next nav_index row
+    pub fn vstack() -> Layout {
+        Layout::new(LayoutKind::VStack)
+    }
+
+    // equal cells filled row by row
+    pub fn grid(columns: usize) -> Layout {
+        Layout::new(LayoutKind::Grid(columns.max(1)))
+    }
+
+    pub fn spacing(mut self, spacing: i32) -> Layout {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn padding(mut self, padding: i32) -> Layout {
+        self.padding = padding;
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> Layout {
+        self.align = align;
+        self
+    }
+
+    pub fn child(mut self, child: LayoutChild) -> Layout {
+        self.children.push(child);
+        self
+    }
+
+    pub fn widget(self, factory: impl FnOnce(i32, i32, i32, i32) -> Box<dyn Gui + Send> + 'static) -> Layout {
+        self.child(LayoutChild::widget(factory))
+    }
+
+    pub fn static_widget(self, factory: impl FnOnce(i32, i32, i32, i32) -> Box<dyn Gui + Send> + 'static) -> Layout {
+        self.child(LayoutChild::static_widget(factory))
+    }
+
+    pub fn nested(self, layout: Layout) -> Layout {
+        self.child(LayoutChild::layout(layout))
+    }
+
+    // build every widget and add it to the view, starting at nav_index (row, column)
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_at(self, view: &mut View, row: usize, column: usize, x: i32, y: i32, w: i32, h: i32) {
+        let Layout {kind, spacing, padding, align, children} = self;
+        let extents: Vec<(usize, usize)> = children.iter().map(|child| child.extent()).collect();
+        let (inner_x, inner_y, inner_w, inner_h) = (x + padding, y + padding, (w - 2 * padding).max(0), (h - 2 * padding).max(0));
+        match kind {
+            LayoutKind::HStack | LayoutKind::VStack => {
+                let horizontal = kind == LayoutKind::HStack;
+                let (main, cross) = if horizontal { (inner_w, inner_h) } else { (inner_h, inner_w) };
+                let sizes = Layout::distribute(&children, main, spacing);
+                let mut offset = 0;
+                let (mut nav_row, mut nav_column) = (row, column);
+                for ((child, size), extent) in children.into_iter().zip(sizes).zip(extents) {
+                    let (cross_offset, cross_len) = Layout::align_cross(child.cross_size, cross, align);
+                    let bounds = if horizontal {
+                        (inner_x + offset, inner_y + cross_offset, size, cross_len)
+                    } else {
+                        (inner_x + cross_offset, inner_y + offset, cross_len, size)
+                    };
+                    child.apply(view, nav_row, nav_column, bounds);
+                    offset += size + spacing;
+                    if horizontal {
+                        nav_column += extent.1;
+                    } else {
+                        nav_row += extent.0;
+                    }
+                }
+            },
+            LayoutKind::Grid(columns) => {
+                let rows = children.len().div_ceil(columns);
+                if rows == 0 {
+                    return;
+                }
+                // spacing wider than the layout leaves empty cells rather than negative ones
+                let cell_w = ((inner_w - spacing * (columns as i32 - 1)) / columns as i32).max(0);
+                let cell_h = ((inner_h - spacing * (rows as i32 - 1)) / rows as i32).max(0);
+                // nav_index rows / columns each grid row / column needs
+                let mut row_extents = vec![0; rows];
+                let mut column_extents = vec![0; columns];
+                for (i, extent) in extents.iter().enumerate() {
+                    row_extents[i / columns] = row_extents[i / columns].max(extent.0);
+                    column_extents[i % columns] = column_extents[i % columns].max(extent.1);
+                }
+                for (i, child) in children.into_iter().enumerate() {
+                    let (grid_row, grid_column) = (i / columns, i % columns);
+                    let (cross_offset, cross_len) = Layout::align_cross(child.cross_size, cell_h, align);
+                    let bounds = (
+                        inner_x + grid_column as i32 * (cell_w + spacing),
+                        inner_y + grid_row as i32 * (cell_h + spacing) + cross_offset,
+                        cell_w,
+                        cross_len
+                    );
+                    let nav_row = row + row_extents[..grid_row].iter().sum::<usize>();
+                    let nav_column = column + column_extents[..grid_column].iter().sum::<usize>();
+                    child.apply(view, nav_row, nav_column, bounds);
+                }
+            }
+        }
+    }
+
+    // rows and columns of nav_index the layout takes up
+    pub fn extent(&self) -> (usize, usize) {
+        let extents: Vec<(usize, usize)> = self.children.iter().map(|child| child.extent()).collect();
+        combine_extents(self.kind, &extents)
+    }
+
+    // fixed sizes first, the rest shared out by weight
+    fn distribute(children: &[LayoutChild], main: i32, spacing: i32) -> Vec<i32> {
+        let gaps = spacing * (children.len() as i32 - 1).max(0);
+        let fixed: i32 = children.iter().map(|child| match child.size {
+            Size::Fixed(size) => size,
+            Size::Fill(_) => 0
+        }).sum();
+        let weights: u32 = children.iter().map(|child| match child.size {
+            Size::Fixed(_) => 0,
+            Size::Fill(weight) => weight
+        }).sum();
+        let free = (main - gaps - fixed).max(0);
+        children.iter().map(|child| match child.size {
+            Size::Fixed(size) => size,
+            Size::Fill(weight) => {
+                if weights == 0 {
+                    0
+                } else {
+                    free * weight as i32 / weights as i32
+                }
+            }
+        }).collect()
+    }
+
+    fn align_cross(cross_size: Option<i32>, cross: i32, align: Align) -> (i32, i32) {
+        match cross_size {
+            Some(size) => {
+                let size = size.min(cross);
+                match align {
+                    Align::Start => (0, size),
+                    Align::Center => ((cross - size) / 2, size),
+                    Align::End => (cross - size, size)
+                }
+            },
+            None => (0, cross)
+        }
+    }
+}
+
+// nav_index rows and columns taken by children laid out as kind
+fn combine_extents(kind: LayoutKind, extents: &[(usize, usize)]) -> (usize, usize) {
+    match kind {
+        LayoutKind::HStack => (extents.iter().map(|e| e.0).max().unwrap_or(0), extents.iter().map(|e| e.1).sum()),
+        LayoutKind::VStack => (extents.iter().map(|e| e.0).sum(), extents.iter().map(|e| e.1).max().unwrap_or(0)),
+        LayoutKind::Grid(columns) => {
+            let mut rows = 0;
+            let mut column_extents = vec![0; columns];
+            for chunk in extents.chunks(columns) {
+                rows += chunk.iter().map(|e| e.0).max().unwrap_or(0);
+                for (c, extent) in chunk.iter().enumerate() {
+                    column_extents[c] = column_extents[c].max(extent.1);
+                }
+            }
+            (rows, column_extents.iter().sum())
+        }
+    }
+}
+
+impl LayoutChild {
+    fn apply(self, view: &mut View, row: usize, column: usize, bounds: (i32, i32, i32, i32)) {
+        let (x, y, w, h) = bounds;
+        match self.content {
+            Content::Widget(factory) => {
+                let object = factory(x, y, w, h);
+                if self.navigable {
+                    view.add_object(object, row, column);
+                } else {
+                    view.add_static_object(object);
+                }
+                if let Some(key) = self.bind {
+                    view.bind(view.objects_len() - 1, key);
+                }
+            },
+            Content::Layout(layout) => layout.apply_at(view, row, column, x, y, w, h)
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    // lay out below anything already in the nav_index
+    pub fn add_layout(&mut self, layout: Layout, x: i32, y: i32, w: i32, h: i32) {
+        let row = self.next_free_row();
+        layout.apply_at(self, row, 0, x, y, w, h);
+    }
+
+    // first nav_index row after the last one in use
+    pub(crate) fn next_free_row(&self) -> usize {
+        self.nav_index.iter().rposition(|row| row.iter().any(|cell| !cell.is_empty()))
+            .map(|last| last + 1)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::{self, Placeholder};
+
+    fn placeholders(layout: Layout, count: usize) -> Layout {
+        (0..count).fold(layout, |layout, _| layout.widget(Placeholder::boxed))
+    }
+
+    // nav_index cell and bounds of an object
+    type Placed = (Option<(usize, usize)>, Option<(i32, i32, i32, i32)>);
+
+    fn placed(view: &View) -> Vec<Placed> {
+        (0..view.objects_len()).map(|object| (view.find_object_cell(object), view.objects[object].bounds())).collect()
+    }
+
+    fn children(sizes: &[Size]) -> Vec<LayoutChild> {
+        sizes.iter().map(|size| LayoutChild::layout(Layout::hstack()).size(*size)).collect()
+    }
+
+    #[test]
+    fn fixed_sizes_come_first_and_fill_shares_the_rest() {
+        let sizes = Layout::distribute(&children(&[Size::Fixed(40), Size::Fill(1), Size::Fill(3)]), 200, 10);
+        assert_eq!(sizes, vec![40, 35, 105]);
+    }
+
+    #[test]
+    fn a_single_child_has_no_gaps() {
+        assert_eq!(Layout::distribute(&children(&[Size::Fill(1)]), 120, 10), vec![120]);
+    }
+
+    #[test]
+    fn overfull_fixed_sizes_leave_fill_empty() {
+        let sizes = Layout::distribute(&children(&[Size::Fixed(150), Size::Fixed(100), Size::Fill(1)]), 200, 4);
+        assert_eq!(sizes, vec![150, 100, 0]);
+    }
+
+    #[test]
+    fn zero_weights_do_not_divide_by_zero() {
+        assert_eq!(Layout::distribute(&children(&[Size::Fill(0), Size::Fill(0)]), 100, 0), vec![0, 0]);
+    }
+
+    #[test]
+    fn fill_never_overflows_the_space() {
+        let sizes = Layout::distribute(&children(&[Size::Fill(1), Size::Fill(1), Size::Fill(1)]), 100, 0);
+        assert!(sizes.iter().sum::<i32>() <= 100);
+    }
+
+    #[test]
+    fn grids_combine_extents_per_row_and_column() {
+        assert_eq!(combine_extents(LayoutKind::Grid(2), &[(1, 1), (2, 1), (1, 3)]), (3, 4));
+        assert_eq!(combine_extents(LayoutKind::HStack, &[(1, 1), (2, 1)]), (2, 2));
+        assert_eq!(combine_extents(LayoutKind::VStack, &[]), (0, 0));
+    }
+
+    #[test]
+    fn stacks_register_children_along_their_axis() {
+        let mut view = test_util::view();
+        placeholders(Layout::hstack(), 2).static_widget(Placeholder::boxed).apply_at(&mut view, 1, 2, 0, 0, 100, 20);
+        let cells: Vec<_> = placed(&view).into_iter().map(|(cell, _)| cell).collect();
+        assert_eq!(cells, vec![Some((1, 2)), Some((1, 3)), None]);
+
+        let mut view = test_util::view();
+        placeholders(Layout::vstack(), 2).apply_at(&mut view, 0, 0, 0, 0, 20, 100);
+        let cells: Vec<_> = placed(&view).into_iter().map(|(cell, _)| cell).collect();
+        assert_eq!(cells, vec![Some((0, 0)), Some((1, 0))]);
+    }
+
+    #[test]
+    fn grids_register_nested_layouts_by_their_extent() {
+        let mut view = test_util::view();
+        Layout::grid(2)
+            .nested(placeholders(Layout::vstack(), 2))
+            .widget(Placeholder::boxed)
+            .widget(Placeholder::boxed)
+            .apply_at(&mut view, 0, 0, 0, 0, 100, 100);
+        let cells: Vec<_> = placed(&view).into_iter().map(|(cell, _)| cell).collect();
+        assert_eq!(cells, vec![Some((0, 0)), Some((1, 0)), Some((0, 1)), Some((2, 0))]);
+    }
+
+    #[test]
+    fn grid_cells_never_go_negative() {
+        let mut view = test_util::view();
+        placeholders(Layout::grid(3).spacing(40), 6).apply_at(&mut view, 0, 0, 0, 0, 50, 30);
+        for (_, bounds) in placed(&view) {
+            let (_, _, w, h) = bounds.unwrap();
+            assert_eq!((w, h), (0, 0));
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use super::canvas::Canvas;
-use super::gui_tk::{Gui,  Event, GuiState, Palette};
-use super::store::{eq_gui_state};
-use std::sync::mpsc::{Sender, Receiver};
-use super::hid::{HIDEvent, IOState};
+use super::gui_tk::{Gui,  Event, GuiState, Palette, GuiConfig};
+use std::sync::mpsc::{Sender, Receiver, channel};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use super::hid::{HIDEvent, IOState, InputEvent, Action, KeyMap};
+use super::store::Action as StoreAction;
 
 
 use std::thread;
@@ -10,6 +12,39 @@ use std::thread::JoinHandle;
 use std::time::{Duration};
 use log::*;
 
+mod modal;
+pub use modal::*;
+
+mod toast;
+pub use toast::*;
+
+mod layout;
+pub use layout::*;
+
+mod view_spec;
+pub use view_spec::*;
+
+mod scroll;
+pub use scroll::*;
+
+mod spatial;
+pub use spatial::*;
+
+mod focus;
+pub use focus::*;
+
+mod history;
+pub use history::*;
+
+mod navigation;
+pub use navigation::*;
+
+mod event_loop;
+pub use event_loop::*;
+
+#[cfg(test)]
+mod test_util;
+
 /*
      ButtonInitializer {pin: 5, code: 0, key: "b"},
      ButtonInitializer {pin: 6, code: 1, key: "a"},
@@ -26,61 +61,9 @@ enum InputMode {
     Manipulate
 }
 
-pub fn run_window_viewer(mut window_viewer: WindowViewer) -> JoinHandle<()>{
-    window_viewer.activate_bar();
-    thread::spawn(move || {
-        loop {
-            match window_viewer.input_receiver.try_recv() {
-                Ok(hid_events) => {
-                    for h_e in &hid_events {
-                        debug!("HIDEvent: {:#?}", h_e);
-                        match window_viewer.handle_hid_event(h_e) {
-                            Some(event) => {
-                                window_viewer.handle_event(event);                
-                            },
-                            None => ()
-                        }
-                    }
-                },
-                Err(_) => ()
-            }
-            let bar_update = window_viewer.update_bar();
-            let active_update = window_viewer.update_active_view();
-            let view_change = window_viewer.window_viewer_updater();
-            if bar_update || active_update || view_change {
-                info!("about to render {}",  window_viewer.active);
-                window_viewer.render();
-            }
-            thread::sleep(Duration::from_millis(5));
-        }
-    })
-}
-
 pub fn gui_state_updater(object: &mut Box<dyn Gui + Send>, new_state: GuiState, canvas: &mut Canvas) {
-    let current_state = object.get_gui_state();
-    if let GuiState::Base = current_state {
-        match new_state {
-            GuiState::Base => (),
-            _ => {
-                object.set_gui_state(new_state.clone(), canvas);
-            }
-        }
-    }
-    if let GuiState::Clicked = current_state {
-        match new_state {
-            GuiState::Clicked => (),
-            _ => {
-                object.set_gui_state(new_state.clone(), canvas);
-            }
-        }
-    }
-    if let GuiState::Selected = current_state {
-        match new_state {
-            GuiState::Selected => (),
-            _ => {
-                object.set_gui_state(new_state.clone(), canvas);
-            }
-        }
+    if object.get_gui_state() != new_state {
+        object.set_gui_state(new_state, canvas);
     }
 }
 
@@ -94,6 +77,23 @@ pub struct ViewSettings {
 pub struct WindowViewer {
     bar: View,
     views: Vec<Box<View>>,
+    // drawn over the active view, the last one gets the input
+    modals: Vec<Modal>,
+    modals_changed: bool,
+    toasts: Toasts,
+    event_notifier: Option<EventNotifier>,
+    // HIDEvent codes to Actions
+    key_map: KeyMap,
+    // global shortcuts, sent whichever view is active
+    action_events: Vec<(Action, Event)>,
+    // views to go back to, see push_view
+    history: Vec<usize>,
+    // navigation Actions forwarded by the store, see forward_navigation
+    navigation_sender: Sender<StoreAction>,
+    navigation_receiver: Receiver<StoreAction>,
+    // set once the loop is running, see wake_source
+    wake: Option<Sender<Wake>>,
+    fps: u32,
     pub active: usize,
     canvas: Canvas,
     input_receiver: Receiver<Vec<HIDEvent>>,
@@ -105,9 +105,23 @@ pub struct WindowViewer {
 impl WindowViewer {
     pub fn new(fbdev: &'static str,  input_receiver: Receiver<Vec<HIDEvent>>, event_sender: Sender<Event>, state_receiver: Receiver<Vec<u8>>, window_viewer_updater_fn: WindowViewerUpdater, info_bar_view: View) -> WindowViewer {
         let canvas: Canvas = Canvas::new(fbdev);
+        let (navigation_sender, navigation_receiver) = channel();
+        let mut toasts = Toasts::new();
+        toasts.default_config_from(&info_bar_view);
         WindowViewer {
             bar: info_bar_view,
             views: vec![],
+            modals: vec![],
+            modals_changed: false,
+            toasts,
+            event_notifier: None,
+            key_map: KeyMap::default(),
+            action_events: vec![],
+            history: vec![],
+            navigation_sender,
+            navigation_receiver,
+            wake: None,
+            fps: DEFAULT_FPS,
             canvas: canvas,
             active: 0,
             input_receiver,
@@ -136,9 +150,33 @@ impl WindowViewer {
 
     // input button handling
     pub fn handle_hid_event(&mut self, h_e: &HIDEvent) -> Option<Event>{
+        let input = self.key_map.map(h_e);
+        self.handle_input(&input)
+    }
+
+    // route an input that has already been through a KeyMap
+    pub fn handle_input(&mut self, h_e: &InputEvent) -> Option<Event>{
+        // a modal captures everything, global shortcuts included
+        if !self.modals.is_empty() {
+            return self.handle_modal_hid_event(h_e);
+        }
+        if h_e.io_state == IOState::Pressed {
+            if let Some((_, event)) = self.action_events.iter().find(|(action, _)| *action == h_e.action) {
+                return Some(event.clone());
+            }
+        }
         if self.views.len() > self.active {
-            match h_e.code {
-                0 => self.set_active_view(0), // go home
+            match h_e.action {
+                Action::Home => {
+                    self.go_home();
+                    None
+                },
+                // back out of the view, if nothing in it has the input
+                Action::Back if h_e.io_state == IOState::Pressed && self.views[self.active].is_navigating() => {
+                    let event = self.views[self.active].escape();
+                    self.pop_view();
+                    event
+                },
                 _ => self.views[self.active].handle_hid_event(h_e)
             }
         } else {
@@ -147,9 +185,53 @@ impl WindowViewer {
     }
 
     pub fn handle_event(&mut self, event: Event) {
+        // navigation events are acted on here, and still passed on
+        if let Some(navigation) = Navigation::from_request(event.name, &event.values) {
+            self.navigate(&navigation);
+        }
+        if let Some(notifier) = self.event_notifier {
+            if let Some(toast) = notifier(&event) {
+                self.notify(toast);
+            }
+        }
         self.event_sender.send(event).unwrap();
     }
 
+    // queue a toast, shown over everything once the ones before it expire
+    pub fn notify(&mut self, toast: Toast) {
+        self.toasts.push(toast);
+    }
+
+    pub fn clear_notifications(&mut self) -> bool {
+        self.toasts.clear(&mut self.canvas)
+    }
+
+    // toasts need a font, by default the bar's or the first view's, set_theme keeps this in step
+    pub fn set_notification_config(&mut self, config: &'static GuiConfig) {
+        self.toasts.set_config(config);
+    }
+
+    // for pads or encoders not wired up like the standard pad
+    pub fn set_key_map(&mut self, key_map: KeyMap) {
+        self.key_map = key_map;
+    }
+
+    // send event whenever action is pressed, ahead of the views but not an open modal, e.g. Custom buttons
+    pub fn bind_action(&mut self, action: Action, event: Event) {
+        self.action_events.retain(|(bound, _)| *bound != action);
+        self.action_events.push((action, event));
+    }
+
+    // turn dispatched events into toasts
+    pub fn set_event_notifier(&mut self, event_notifier: EventNotifier) {
+        self.event_notifier = Some(event_notifier);
+    }
+
+    // called every loop, raise after anything else was redrawn
+    pub fn update_toasts(&mut self, raise: bool) -> bool {
+        self.toasts.update(&mut self.canvas, raise)
+    }
+
 
     // draw it out
     pub fn render(&mut self) {
@@ -167,45 +249,160 @@ impl WindowViewer {
     }
 
     // this is a move operation
-    pub fn add_view(&mut self, view: View) {
+    pub fn add_view(&mut self, mut view: View) {
         if view.objects_len() > 0 {
+            if let Some(wake) = &self.wake {
+                view.relay_state(wake);
+            }
+            self.toasts.default_config_from(&view);
             self.views.push(Box::new(view));
         } else {
             panic!("Cannot add a view with 0 objects")
         }
     }
 
+    // add an object to a view that may already be on screen
+    // cell is the nav_index row and column, None adds a static object
+    pub fn add_view_object(&mut self, view: usize, mut object: Box<dyn Gui + Send>, cell: Option<(usize, usize)>) -> bool {
+        if self.views.len() <= view {
+            debug!("Cannot add an object to non existent view {}", view);
+            return false;
+        }
+        object.initialize(&mut self.canvas);
+        let target = &mut self.views[view];
+        match cell {
+            Some((row, column)) => target.add_object(object, row, column),
+            None => target.add_static_object(object)
+        }
+        if view == self.active {
+            let index = target.objects_len() - 1;
+            target.objects[index].activate(&mut self.canvas);
+            target.ensure_selection();
+            target.stale = true;
+        }
+        true
+    }
+
+    // remove an object from a view, later objects in that view move down one index
+    pub fn remove_view_object(&mut self, view: usize, object: usize) -> Option<Box<dyn Gui + Send>> {
+        match self.views.get_mut(view) {
+            Some(target) => target.remove_object(object, &mut self.canvas),
+            None => None
+        }
+    }
+
     pub fn update_active_view(&mut self) -> bool{
         if self.views.len() > self.active {
-            if self.views[self.active].update( &mut self.canvas) {
-                true
-            } else {
-                false
-            }
+            let view_updated = self.views[self.active].update( &mut self.canvas);
+            let modals_updated = self.update_modals(view_updated);
+            view_updated || modals_updated
         } else {
             panic!("Cannot update a view which does not exist");
         }
    
     }
 
+    // open a modal over the active view, it gets all input until it closes
+    pub fn push_modal(&mut self, mut modal: Modal) {
+        if modal.view.objects_len() == 0 {
+            debug!("Cannot push a modal with 0 objects");
+            return;
+        }
+        if let Some(wake) = &self.wake {
+            modal.view.relay_state(wake);
+        }
+        modal.view.initialize(&mut self.canvas);
+        modal.view.activate(&mut self.canvas);
+        self.modals.push(modal);
+        self.modals_changed = true;
+    }
+
+    // close the top modal, the one below (or the active view) gets the input back
+    pub fn pop_modal(&mut self) -> bool {
+        match self.modals.pop() {
+            Some(mut modal) => {
+                modal.view.deactivate(&mut self.canvas);
+                modal.view.drop_layers(&mut self.canvas);
+                self.modals_changed = true;
+                true
+            },
+            None => false
+        }
+    }
+
+    pub fn has_modal(&self) -> bool {
+        !self.modals.is_empty()
+    }
+
+    fn handle_modal_hid_event(&mut self, h_e: &InputEvent) -> Option<Event> {
+        let modal = self.modals.last_mut()?;
+        match (h_e.action, &h_e.io_state, &modal.view.input_mode) {
+            // home is ignored while a modal is open
+            (Action::Home, _, _) => None,
+            // back closes the modal
+            (Action::Back, IOState::Pressed, InputMode::Navigate) => {
+                let cancel_event = modal.cancel_event.clone();
+                self.pop_modal();
+                cancel_event
+            },
+            _ => {
+                let event = modal.view.handle_hid_event(h_e);
+                if event.is_some() && modal.close_on_event {
+                    self.pop_modal();
+                }
+                event
+            }
+        }
+    }
+
+    // modals stay on top of anything the view below redrew
+    fn update_modals(&mut self, view_updated: bool) -> bool {
+        let mut updated = self.modals_changed;
+        self.modals_changed = false;
+        for modal in self.modals.iter_mut() {
+            if modal.view.update(&mut self.canvas) {
+                updated = true;
+            }
+        }
+        if updated || view_updated {
+            for modal in self.modals.iter() {
+                for group in modal.view.layer_groups() {
+                    self.canvas.raise_layer_group(group);
+                }
+            }
+        }
+        updated
+    }
+
     // for user input routing
+    // a jump, the history is left as it is, see push_view
     pub fn set_active_view(&mut self, view: usize) -> Option<Event>{
         if self.views.len() <= view {
-            panic!("Cannot activate a view which does not exist");
-        }
-        for i in (0 as usize)..self.views.len() {
-            if i == view {
-                self.active = view;
-                info!("activating {}", self.active);
-                self.views[self.active].activate(&mut self.canvas);
-            } else {
-                info!("deactivating {}", i);
-                self.views[i].deactivate(&mut self.canvas);
-            }
+            warn!("Cannot activate view {} which does not exist", view);
+            return None;
         }
+        self.switch_view(view, false);
         None
     }
 
+    // switch every view (and the bar) over to a new theme
+    // the objects regenerate their layers, then the active view is restored
+    // see GuiConfig::cached for themes switched at runtime
+    pub fn set_theme(&mut self, config: &'static GuiConfig) {
+        self.toasts.set_config(config);
+        self.bar.set_config(config, &mut self.canvas);
+        self.bar.activate(&mut self.canvas);
+        for i in 0..self.views.len() {
+            self.views[i].set_config(config, &mut self.canvas);
+        }
+        for modal in self.modals.iter_mut() {
+            modal.view.set_config(config, &mut self.canvas);
+        }
+        if self.views.len() > self.active {
+            self.show_view(self.active, true);
+        }
+    }
+
     pub fn bar_stale(&self) -> bool {
         self.bar.stale
     }
@@ -220,14 +417,32 @@ impl WindowViewer {
 
 
 pub struct View {
+    // see WindowViewer::add_named_view
+    name: Option<&'static str>,
     objects: Vec<Box<dyn Gui + Send>>,
     input_mode: InputMode,
+    nav_mode: NavMode,
     nav_index: Vec<Vec<Vec<usize>>>,
+    // horizontal, vertical
+    wrap: (bool, bool),
+    // from, direction, to, see View::set_focus
+    focus_overrides: Vec<(usize, Direction, usize)>,
     selected_row: usize,
     selected_column: usize,
     selected_object: usize,
     state_receiver: Receiver<Vec<u8>>,
+    // on screen, state for views that are not only wakes the loop once they are
+    shown: Arc<AtomicBool>,
     update_fn: ViewStateUpdater,
+    // objects fed from the state by key, see View::bind
+    bindings: Vec<(usize, &'static str)>,
+    binder: Option<StateBinder>,
+    // set by with_scroll for content larger than the screen
+    scroll: Option<Scroll>,
+    on_enter: Option<ViewHook>,
+    on_leave: Option<ViewHook>,
+    entered: bool,
+    escape_event: Option<Event>,
     gui_state: Vec<GuiState>,
     stale: bool
 }
@@ -236,40 +451,216 @@ pub type WindowViewerUpdater = fn(&mut WindowViewer, &[u8]) -> bool;
   
 pub type ViewStateUpdater = fn(&mut  Vec<Box<dyn Gui + Send>>, &[u8], &mut Canvas );
 
+// what a bound key resolves to in the current state
+#[derive(Clone, Debug, PartialEq)]
+pub enum Binding {
+    Text(String),
+    Value(f64),
+    // series, value
+    Push(usize, f64),
+    Checked(bool),
+    Enabled(bool)
+}
+
+// looks a key up in the serialized state, the app knows how to decode it
+pub type StateBinder = fn(&[u8], &str) -> Option<Binding>;
+
 impl View {
     pub fn new(update_fn: ViewStateUpdater, state_receiver: Receiver<Vec<u8>>) -> View {
         let objects: Vec<Box<dyn Gui + Send>> = vec![];
-        let nav_index: Vec<Vec<Vec<usize>>> = vec![vec![vec![0;0]; 4]; 16];
+        // grows to fit the rows and columns add_object is given
+        let nav_index: Vec<Vec<Vec<usize>>> = vec![];
 
         let selected_row = 0;
         let selected_column = 0;
         let selected_object =  0;   
         View {
+            name: None,
             objects,
             input_mode: InputMode::Navigate,
+            nav_mode: NavMode::Grid,
             nav_index,
+            wrap: (false, false),
+            focus_overrides: vec![],
             selected_row,
             selected_column,
             selected_object,
             state_receiver,
+            shown: Arc::new(AtomicBool::new(false)),
             update_fn,
+            bindings: vec![],
+            binder: None,
+            scroll: None,
+            on_enter: None,
+            on_leave: None,
+            entered: false,
+            escape_event: None,
             gui_state: vec![],
             stale: false
         }
     }
 
+    pub fn with_binder(mut self, binder: StateBinder) -> View {
+        self.binder = Some(binder);
+        self
+    }
+
+    pub fn set_binder(&mut self, binder: StateBinder) {
+        self.binder = Some(binder);
+    }
+
+    // feed an object from the state key on every update, after the update_fn
+    pub fn bind(&mut self, object: usize, key: &'static str) {
+        if object < self.objects.len() {
+            self.bindings.push((object, key));
+        } else {
+            debug!("Cannot bind {} to non existent object {}", key, object);
+        }
+    }
+
+    fn apply_bindings(&mut self, state: &[u8], canvas: &mut Canvas) {
+        let binder = match self.binder {
+            Some(binder) => binder,
+            None => return
+        };
+        for (object, key) in self.bindings.iter() {
+            let object = &mut self.objects[*object];
+            match binder(state, key) {
+                Some(Binding::Text(text)) => {
+                    if object.get_text() != text {
+                        object.set_text(text, canvas);
+                    }
+                },
+                Some(Binding::Value(value)) => object.set_value(value, canvas),
+                Some(Binding::Push(series, value)) => object.push_value(series, value, canvas),
+                Some(Binding::Checked(checked)) => object.set_checked(checked, canvas),
+                Some(Binding::Enabled(enabled)) => {
+                    if enabled != object.get_gui_state().is_enabled() {
+                        let gui_state = if enabled { object.idle_gui_state() } else { GuiState::Disabled };
+                        object.set_gui_state(gui_state, canvas);
+                    }
+                },
+                None => trace!("No state for binding {}", key)
+            }
+        }
+    }
+
     // add a navigable object to the view
     pub fn add_object(&mut self, object: Box<dyn Gui + Send>, row: usize, column: usize ) {
         let object_index = self.objects.len(); //
 
         self.gui_state.push(object.get_gui_state());
         self.objects.push(object);
-        if self.nav_index.len() > row && self.nav_index[row].len() > column {
-            self.nav_index[row][column].push(object_index);
-        } else {
-            panic!("Row and Column outside bounds");
+        if self.nav_index.len() <= row {
+            self.nav_index.resize(row + 1, vec![]);
+        }
+        if self.nav_index[row].len() <= column {
+            self.nav_index[row].resize(column + 1, vec![]);
+        }
+        self.nav_index[row][column].push(object_index);
+    }
+
+    // take an object out of the view and off the canvas
+    // objects after it move down one index, bindings and the nav_index follow
+    // if it was selected the selection moves to the closest object left
+    pub fn remove_object(&mut self, object: usize, canvas: &mut Canvas) -> Option<Box<dyn Gui + Send>> {
+        let mut removed = self.detach_object(object)?;
+        removed.deactivate(canvas);
+        for group in removed.layer_groups() {
+            canvas.clear_viewport(group.clone());
+            canvas.drop_layer_group(group);
+        }
+        Some(removed)
+    }
+
+    // take an object out of the nav_index, bindings, focus and selection
+    // its layers are left on the canvas
+    fn detach_object(&mut self, object: usize) -> Option<Box<dyn Gui + Send>> {
+        if object >= self.objects.len() {
+            debug!("Cannot remove non existent object {}", object);
+            return None;
+        }
+        let removed_cell = self.find_object_cell(object);
+        let removed = self.objects.remove(object);
+        self.gui_state.remove(object);
+
+        for row in self.nav_index.iter_mut() {
+            for cell in row.iter_mut() {
+                cell.retain(|index| *index != object);
+                for index in cell.iter_mut() {
+                    if *index > object {
+                        *index -= 1;
+                    }
+                }
+            }
+        }
+        self.reindex_focus(object);
+        self.bindings.retain(|(index, _)| *index != object);
+        for (index, _) in self.bindings.iter_mut() {
+            if *index > object {
+                *index -= 1;
+            }
+        }
+
+        if self.selected_object > object {
+            self.selected_object -= 1;
+        } else if self.selected_object == object {
+            self.input_mode = InputMode::Navigate;
+            let (row, column) = removed_cell.unwrap_or((self.selected_row, self.selected_column));
+            match self.nearest_selectable(row, column) {
+                Some((item, r_index, c_index)) => {
+                    self.selected_object = item;
+                    self.selected_row = r_index;
+                    self.selected_column = c_index;
+                    self.move_selection();
+                },
+                None => self.selected_object = 0
+            }
+        }
+        self.stale = true;
+        Some(removed)
+    }
+
+    // nav cell an object was added to, None for static objects
+    pub fn find_object_cell(&self, object: usize) -> Option<(usize, usize)> {
+        for (r_index, row) in self.nav_index.iter().enumerate() {
+            for (c_index, cell) in row.iter().enumerate() {
+                if cell.contains(&object) {
+                    return Some((r_index, c_index));
+                }
+            }
+        }
+        None
+    }
+
+    // select something if nothing navigable is, e.g. after the first add at runtime
+    fn ensure_selection(&mut self) {
+        if self.find_object_cell(self.selected_object).is_some() {
+            return;
+        }
+        if let Some((item, r_index, c_index)) = self.nearest_selectable(0, 0) {
+            self.selected_object = item;
+            self.selected_row = r_index;
+            self.selected_column = c_index;
+            self.move_selection();
+        }
+    }
+
+    // closest selectable object to a nav cell, its own row first then rows further out
+    fn nearest_selectable(&self, row: usize, column: usize) -> Option<(usize, usize, usize)> {
+        for offset in 0..self.nav_index.len() {
+            let below = row + offset;
+            let above = row.checked_sub(offset);
+            for r_index in [Some(below), above].iter().flatten() {
+                if *r_index >= self.nav_index.len() {
+                    continue;
+                }
+                if let Some(c_index) = self.closest_column(*r_index, column) {
+                    return Some((self.cell(*r_index, c_index)[0], *r_index, c_index));
+                }
+            }
         }
-        
+        None
     }
 
     // add a static - non navigable object to the view
@@ -278,36 +669,37 @@ impl View {
         self.gui_state.push(object.get_gui_state());
         self.objects.push(object);
     }
+    // back pressed while navigating, WindowViewer also returns to the previous view
     pub fn escape(&mut self) -> Option<Event>{
-        None
+        self.escape_event.clone()
     }
 
 
 
-    pub fn nav(&mut self, h_e: &HIDEvent) -> Option<Event> {
-        match h_e.code {
-            2 => {
+    pub fn nav(&mut self, h_e: &InputEvent) -> Option<Event> {
+        match h_e.action {
+            Action::Left => {
                     match h_e.io_state { 
-                        IOState::Pressed => self.h_move(-1),
+                        IOState::Pressed => self.move_direction(-1, 0),
                         _ => ()
                     }
                 },
-            3 => {
+            Action::Right => {
                     match h_e.io_state { 
-                        IOState::Pressed => self.h_move(1),
+                        IOState::Pressed => self.move_direction(1, 0),
                         _ => ()
                     }
 
                 }, 
-            4 => {
+            Action::Up => {
                     match h_e.io_state { 
-                        IOState::Pressed => self.v_move(-1),
+                        IOState::Pressed => self.move_direction(0, -1),
                         _ => ()
                     }
                 },
-            5 => {
+            Action::Down => {
                     match h_e.io_state { 
-                        IOState::Pressed => self.v_move(1),
+                        IOState::Pressed => self.move_direction(0, 1),
                         _ => ()
 
                     }
@@ -319,6 +711,61 @@ impl View {
         None
     }
 
+    fn move_direction(&mut self, dx: isize, dy: isize) {
+        if self.focus_override(dx, dy) {
+            return;
+        }
+        let original_selected_object = self.selected_object;
+        // objects without bounds can only be left through the grid
+        let spatial = self.nav_mode == NavMode::Spatial
+            && !self.objects.is_empty()
+            && self.objects[self.selected_object].bounds().is_some();
+        if spatial {
+            self.spatial_move(dx, dy);
+        } else if dx != 0 {
+            self.h_move(dx);
+        } else {
+            self.v_move(dy);
+        }
+        // nothing further that way
+        let wrap = if dx != 0 { self.wrap.0 } else { self.wrap.1 };
+        if wrap && original_selected_object == self.selected_object {
+            if spatial {
+                self.spatial_wrap(dx, dy);
+            } else {
+                self.grid_wrap(dx, dy);
+            }
+        }
+    }
+
+    // the navigable (not disabled) objects in a nav cell
+    fn cell(&self, row: usize, column: usize) -> Vec<usize> {
+        match self.nav_index.get(row).and_then(|r| r.get(column)) {
+            Some(cell) => cell.iter().cloned().filter(|object| self.gui_state[*object].is_enabled()).collect(),
+            None => vec![]
+        }
+    }
+
+    // closest column to `column` in `row` that has something to select
+    fn closest_column(&self, row: usize, column: usize) -> Option<usize> {
+        let row_length = self.nav_index[row].len();
+        for offset in 0..row_length.max(column + 1) {
+            // pos
+            let pos_attempt = column + offset;
+            if pos_attempt < row_length && !self.cell(row, pos_attempt).is_empty() {
+                return Some(pos_attempt);
+            }
+            // neg
+            if offset <= column {
+                let neg_attempt = column - offset;
+                if neg_attempt < row_length && !self.cell(row, neg_attempt).is_empty() {
+                    return Some(neg_attempt);
+                }
+            }
+        }
+        None
+    }
+
     pub fn v_move(&mut self, amount: isize) {
         // orig selected
         let original_selected_object = self.selected_object;
@@ -327,112 +774,52 @@ impl View {
         let row_count = self.nav_index.len() as isize;
         let attempted_row = self.selected_row as isize + amount;
         trace!("Trying Row: {}", attempted_row);
-        if attempted_row >= 0 && attempted_row < row_count {
-            trace!(" - Row Exists");
-            // the attempted row exists
-            let attempted_row_length = self.nav_index[attempted_row as usize].len();
-            // does the selected column exist?
-            if self.selected_column < attempted_row_length && attempted_row_length > 0 {
-                trace!("   - Current Column exists in this row");
-                if self.nav_index[attempted_row as usize ][self.selected_column].len() > 0 {
-                    trace!("     - Current Column Has Objects");
-                    // is there anything in that column?
-                    self.selected_row = attempted_row as usize;
-                    self.selected_object = self.nav_index[self.selected_row][self.selected_column][0];
-                } else {
-                    trace!("      - Current Column has not objects - check the entire row");
-                    // we move out adding and subtracting till we hit something
-                    for offset in 1..attempted_row_length as isize { 
-                        // pos
-                        let pos_attempt = self.selected_column as isize + offset;
-                        if pos_attempt >= 0 && (pos_attempt as usize) < attempted_row_length {
-                            if self.nav_index[attempted_row as usize][pos_attempt as usize].len() > 0 {
-                                self.selected_row = attempted_row as usize;
-                                self.selected_column = pos_attempt as usize;
-                                self.selected_object = self.nav_index[self.selected_row][self.selected_column][0];
-                                break;
-                            }
-                        }
-                        // neg
-                        let neg_attempt = self.selected_column as isize - offset;
-                        if neg_attempt >= 0 && (neg_attempt as usize) < attempted_row_length {
-                            if self.nav_index[attempted_row as usize][neg_attempt as usize].len() > 0 {
-                                self.selected_row = attempted_row as usize;
-                                self.selected_column = neg_attempt as usize;
-                                self.selected_object = self.nav_index[self.selected_row][self.selected_column][0];
-                                break;
-                            }
-                        } 
-                    }
-                    if  original_selected_object == self.selected_object {
-                        trace!("     - Row Appears Empty. Let's check the next row in this direction");
-                        // we didn't hit anything recurse
-                        if amount > 0 {
-                            self.v_move(amount + 1)
-                        } else if amount < 0 {
-                            self.v_move(amount - 1);
-                        }
-                    }
-                }
-
-            } else if attempted_row_length > 0 { // there is stuff in here but we had hit a column with nothing in it
-                trace!("     - We went to a row with existing columns, but exceeded the column count");
-                // go to last column we can
-                // if selected_column was out of bounds // try the greatest column on down to see if we could get anything
-                    for attempted_column in (0..attempted_row_length).rev() {
-                        if self.nav_index[attempted_row as usize][attempted_column as usize].len() > 0 {
-                            self.selected_row = attempted_row as usize;
-                            self.selected_column = attempted_column as usize;
-                            self.selected_object = self.nav_index[self.selected_row][self.selected_column][0];
-                            break;
-                        }
-                    }
-                if  original_selected_object == self.selected_object {
-                    trace!("     - There was still nothing in this row. Try the next row in this direction");
-                    if original_selected_object != self.selected_object && amount > 0 {
-                        self.v_move(amount + 1)
-                    } else if original_selected_object != self.selected_object && amount < 0 {
-                        self.v_move(amount - 1);
-                    }
-                }
-            } else {// nothing in that row we could use... try another row up or down
-                trace!("     - We hit a row with 0 columns, keep moving to the next row");
+        if attempted_row < 0 || attempted_row >= row_count {
+            // attempted row is out of bounds .. stay where we are
+            return;
+        }
+        match self.closest_column(attempted_row as usize, self.selected_column) {
+            Some(column) => {
+                trace!(" - Found column {} in row {}", column, attempted_row);
+                self.selected_row = attempted_row as usize;
+                self.selected_column = column;
+                self.selected_object = self.cell(self.selected_row, self.selected_column)[0];
+            },
+            None => {
+                trace!("     - Nothing selectable in this row, keep moving to the next row");
                 if amount > 0 {
                     self.v_move(amount + 1)
                 } else if amount < 0 {
                     self.v_move(amount - 1);
                 }
+                return;
             }
-            // if change send reducer
-            if original_selected_object != self.selected_object {
-//                self.action_sender.send( Action::new("[Move Selection To]", self.name.clone(), self.selected_object as isize) ).unwrap();
-                self.move_selection();
-            }
-            
-        } // attempted row is out of bounds .. stay where we are
+        }
+        // if change send reducer
+        if original_selected_object != self.selected_object {
+            self.move_selection();
+        }
     }
 
     fn h_cell_move(&mut self, amount: isize) {
-        let row_length = self.nav_index[self.selected_row].len() as isize;
+        let row_length = self.nav_index.get(self.selected_row).map_or(0, |row| row.len()) as isize;
         let attempted_column: isize =  (self.selected_column as isize) + amount;
         if attempted_column < row_length && attempted_column >= 0 {
-            let cell_length =  self.nav_index[self.selected_row][attempted_column as usize].len();  
-            if cell_length > 0 {
+            let cell = self.cell(self.selected_row, attempted_column as usize);
+            if !cell.is_empty() {
                 // new column is good
                 self.selected_column = attempted_column as usize; // set the selected_column
                 if amount < 0 { // moving left
                     // selected = greatest
-                    self.selected_object = self.nav_index[self.selected_row][self.selected_column][cell_length - 1];
+                    self.selected_object = cell[cell.len() - 1];
                 } else if amount > 0 { // moving right
                     // selected = smallest 
-                    self.selected_object = self.nav_index[self.selected_row][self.selected_column][0];
-                }
-            } else {
-                if amount > 0 {
-                    self.h_cell_move(amount + 1);
-                } else if amount < 0 {
-                    self.h_cell_move( amount - 1);
+                    self.selected_object = cell[0];
                 }
+            } else if amount > 0 {
+                self.h_cell_move(amount + 1);
+            } else if amount < 0 {
+                self.h_cell_move( amount - 1);
             }
         } // tried to move too far we bail and just stay where we are
     }
@@ -441,33 +828,28 @@ impl View {
         // orig selected
         let original_selected_object = self.selected_object;
         // current cell
-        let current_cell_length = self.nav_index[self.selected_row][self.selected_column].len();
-        let current_cell_index: usize;
-        match self.nav_index[self.selected_row][self.selected_column].iter().position(|x| *x == self.selected_object) {
-            Some(x) => current_cell_index = x,
-            None => {
-                panic!("Could not find any objects matching selected_object: {}", self.selected_object);
-            }
-        }
-        // move left one in cell
-        if (current_cell_index as isize) >= 1 && amount < 0 {
-            self.selected_object = self.nav_index[self.selected_row][self.selected_column][current_cell_index - 1];
-        // move once cell left
-        } else if (current_cell_index as isize) < 1 && amount < 0 {
-            self.h_cell_move(-1);
-        // move right one in cell
-        } else if (current_cell_index as isize) + amount < current_cell_length as isize && amount > 0 {
-            self.selected_object = self.nav_index[self.selected_row][self.selected_column][current_cell_index + 1];          
-        } else if (current_cell_index as isize) + amount >= current_cell_length as isize && amount > 0 {
-            self.h_cell_move(1);
+        let cell = self.cell(self.selected_row, self.selected_column);
+        match cell.iter().position(|x| *x == self.selected_object) {
+            // move left one in cell
+            Some(index) if amount < 0 && index >= 1 => {
+                self.selected_object = cell[index - 1];
+            },
+            // move right one in cell
+            Some(index) if amount > 0 && index + 1 < cell.len() => {
+                self.selected_object = cell[index + 1];
+            },
+            // move one cell over
+            _ => self.h_cell_move(amount.signum())
         }
         if original_selected_object != self.selected_object {
-//            self.action_sender.send( Action::new("[Move Selection To]", self.name.clone(), self.selected_object as isize) ).unwrap();
             self.move_selection();
         }
     }
     
-    pub fn send_to_selected(&mut self, h_e: &HIDEvent) -> Option<Event>{
+    pub fn send_to_selected(&mut self, h_e: &InputEvent) -> Option<Event>{
+        if self.objects.is_empty() || !self.gui_state[self.selected_object].is_enabled() {
+            return None;
+        }
         let (return_control, o_gui_state, event) = self.objects[self.selected_object].handle_hid_event(h_e);
         match o_gui_state {
             Some(gui_state) => self.set_gui_state(gui_state), 
@@ -492,18 +874,36 @@ impl View {
     }
 
     fn activate(&mut self, canvas: &mut Canvas) -> bool {
+        self.show(false, canvas)
+    }
+
+    // activate, keeping the selection from last time if it is still selectable
+    fn resume(&mut self, canvas: &mut Canvas) -> bool {
+        self.show(true, canvas)
+    }
+
+    fn show(&mut self, resume: bool, canvas: &mut Canvas) -> bool {
         for i in (0 as usize)..self.objects.len() {
             if !self.objects[i].activate(canvas) {
                 return false;
             }
         }
+        self.activate_scroll(canvas);
+        self.shown.store(true, Ordering::Relaxed);
+        self.cancel_edit();
         // set first object as selected upon activation
         if self.objects.len() > 0 {
-            // find what cell that object is in
-            let (item, r_index, c_index) = self.find_prime_object();
-            self.selected_object = item;
-            self.selected_row = r_index;
-            self.selected_column = c_index;
+            let keep = resume
+                && self.selected_object < self.objects.len()
+                && self.gui_state[self.selected_object].is_enabled()
+                && self.find_object_cell(self.selected_object).is_some();
+            if !keep {
+                // find what cell that object is in
+                let (item, r_index, c_index) = self.find_prime_object();
+                self.selected_object = item;
+                self.selected_row = r_index;
+                self.selected_column = c_index;
+            }
             self.move_selection();
         }
         true
@@ -512,17 +912,29 @@ impl View {
 
     fn find_prime_object(&self) -> (usize, usize, usize) {
         for (r_index, row) in self.nav_index.iter().enumerate() {
-            for (c_index, column) in row.iter().enumerate() {
-                if column.len() > 0 {
-                    for item in column {
-                        return (*item, r_index, c_index)
-                    }
+            for c_index in 0..row.len() {
+                if let Some(item) = self.cell(r_index, c_index).first() {
+                    return (*item, r_index, c_index)
                 }
             }
         }
         (0, 0 ,0)
     }
 
+    // the updater may have disabled the selected object, move off of it
+    fn repair_selection(&mut self) {
+        if self.objects.is_empty() || self.gui_state[self.selected_object].is_enabled() {
+            return;
+        }
+        let (item, r_index, c_index) = self.find_prime_object();
+        if self.gui_state[item].is_enabled() {
+            self.selected_object = item;
+            self.selected_row = r_index;
+            self.selected_column = c_index;
+            self.move_selection();
+        }
+    }
+
     fn update(&mut self, canvas: &mut Canvas) -> bool {
         // if the view's gui is not yet in sync
         let mut gui_state_updated = false;
@@ -541,6 +953,12 @@ impl View {
             Ok(state) => {
                 let update_fn_actor: ViewStateUpdater = self.update_fn;
                 update_fn_actor(&mut self.objects, &state[..], canvas);
+                self.apply_bindings(&state[..], canvas);
+                // the updater may have enabled or disabled objects
+                for i in 0..self.objects.len() {
+                    self.gui_state[i] = self.objects[i].get_gui_state();
+                }
+                self.repair_selection();
                 debug!("Called update on active view -- should mean state changes");
                 true 
             },
@@ -548,10 +966,44 @@ impl View {
                 false
             }
         };
-        updated || gui_state_updated
+
+        // let objects catch up on changes from input handling or the updater
+        let mut refreshed = false;
+        for object in self.objects.iter_mut() {
+            if object.refresh(canvas) {
+                refreshed = true;
+            }
+        }
+        let scrolled = self.update_scroll(updated || gui_state_updated || refreshed, canvas);
+        updated || gui_state_updated || refreshed || scrolled
     }
     
+    pub fn set_config(&mut self, config: &'static GuiConfig, canvas: &mut Canvas) {
+        for object in self.objects.iter_mut() {
+            object.set_config(config, canvas);
+        }
+        self.set_scroll_config(config);
+    }
+
+    // every canvas layer group owned by the view's objects
+    fn layer_groups(&self) -> Vec<String> {
+        let mut groups: Vec<String> = self.objects.iter().flat_map(|object| object.layer_groups()).collect();
+        groups.append(&mut self.scroll_layer_groups());
+        groups
+    }
+
+    // take the view's objects off the canvas for good
+    fn drop_layers(&mut self, canvas: &mut Canvas) {
+        for group in self.layer_groups() {
+            canvas.clear_viewport(group.clone());
+            canvas.drop_layer_group(group);
+        }
+    }
+
     fn deactivate(&mut self, canvas: &mut Canvas) -> bool {
+        self.shown.store(false, Ordering::Relaxed);
+        self.cancel_edit();
+        self.deactivate_scroll(canvas);
         for i in (0 as usize)..self.objects.len() {
             if !self.objects[i].deactivate(canvas) {
                 debug!("Attempt to deactivate gui object returned false");
@@ -561,14 +1013,15 @@ impl View {
         true
     }
 
-    fn handle_hid_event(&mut self, h_e: &HIDEvent) -> Option<Event> {
+    fn handle_hid_event(&mut self, h_e: &InputEvent) -> Option<Event> {
         // nav mode or manipulate mode?
         match self.input_mode {
             InputMode::Navigate => {
-                match h_e.code {
-                    0 => None, // go home -- should be handled by root
-                    1 => self.escape(),
-                    6 => self.send_to_selected(h_e),
+                match h_e.action {
+                    Action::Home => None, // go home -- should be handled by root
+                    // only once per press, the release may reach a view that was just switched to
+                    Action::Back if h_e.io_state == IOState::Pressed => self.escape(),
+                    Action::Select => self.send_to_selected(h_e),
                     _ => self.nav(h_e)
                 }
             }, // up / down / right / left will move the selection from widget to widget -- b = home, a = back == home
@@ -585,29 +1038,30 @@ impl View {
     // mutate gui_state
 
     fn move_selection(&mut self) -> bool {
-        if self.gui_state.len() == 0 {
+        if self.gui_state.is_empty() {
             debug!("Attempt to move selection when no available objects");       
             return false
         }
-        let current = self.gui_state.iter().position(|x| match x { 
-            GuiState::Selected => true,
-            _ => false
-        });
-        let changed = match current {
-            Some(position) => {
-                self.gui_state[position] = GuiState::Base;
-                if position != self.selected_object as usize {
-                    true
-                } else {
-                    false
-                }
-            },
-            _ => true
-        };
-        self.gui_state[self.selected_object] = GuiState::Selected;
+        if !self.gui_state[self.selected_object].is_enabled() {
+            debug!("Attempt to select a disabled object");
+            return false
+        }
+        let mut changed = false;
+        for position in 0..self.gui_state.len() {
+            let current = &self.gui_state[position];
+            if position != self.selected_object && (*current == GuiState::Selected || *current == GuiState::Clicked) {
+                self.gui_state[position] = self.objects[position].idle_gui_state();
+                changed = true;
+            }
+        }
+        if self.gui_state[self.selected_object] != GuiState::Selected {
+            self.gui_state[self.selected_object] = GuiState::Selected;
+            changed = true;
+        }
         if changed {
             self.stale = true;
         }
+        self.scroll_to_selected();
         changed
     }
 
@@ -617,7 +1071,7 @@ impl View {
             return false
         }
 
-        if eq_gui_state(&self.gui_state[self.selected_object],& gui_state) {
+        if self.gui_state[self.selected_object] == gui_state {
             false
         } else {
             self.gui_state[self.selected_object] = gui_state;
@@ -626,3 +1080,64 @@ impl View {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::test_util::*;
+
+    #[test]
+    fn removing_reindexes_later_objects() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert!(view.detach_object(1).is_some());
+        assert_eq!(view.objects_len(), 3);
+        assert_eq!(view.find_object_cell(1), Some((1, 0)));
+        assert_eq!(view.find_object_cell(2), Some((1, 1)));
+        assert_eq!(view.navigable_objects(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn removing_reindexes_bindings_and_focus() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        view.bind(0, "first");
+        view.bind(1, "second");
+        view.bind(2, "third");
+        view.set_focus(2, Direction::Down, 0);
+        view.set_focus(0, Direction::Up, 1);
+        view.detach_object(1);
+        assert_eq!(view.bindings, vec![(0, "first"), (1, "third")]);
+        assert_eq!(view.focus_overrides, vec![(1, Direction::Down, 0)]);
+    }
+
+    #[test]
+    fn removing_the_selection_moves_to_the_nearest_object() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        assert!(view.select_object(1));
+        view.detach_object(1);
+        assert!(view.selected_object < view.objects_len());
+        assert_eq!(view.find_object_cell(view.selected_object), Some((view.selected_row, view.selected_column)));
+    }
+
+    #[test]
+    fn removing_before_the_selection_keeps_it() {
+        let mut view = grid_view(&[(0, 0), (0, 1), (0, 2)]);
+        assert!(view.select_object(2));
+        view.detach_object(0);
+        assert_eq!(view.selected_object, 1);
+        assert_eq!(view.find_object_cell(1), Some((0, 2)));
+    }
+
+    #[test]
+    fn removing_out_of_range_does_nothing() {
+        let mut view = grid_view(&[(0, 0)]);
+        assert!(view.detach_object(3).is_none());
+        assert_eq!(view.objects_len(), 1);
+    }
+
+    #[test]
+    fn the_nav_grid_grows_to_fit() {
+        let view = grid_view(&[(3, 2)]);
+        assert_eq!(view.find_object_cell(0), Some((3, 2)));
+        assert_eq!(view.navigable_objects(), vec![0]);
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+use super::super::gui_tk::{Block, Button, TextBlock, Style};
+use std::sync::mpsc::channel;
+
+// Modal
+// a view pushed over the active view by WindowViewer::push_modal
+// it captures all input until it closes, either when one of its objects
+// emits an event or when back is pressed in navigate mode
+pub struct Modal {
+    pub view: View,
+    // closes as soon as an object emits an event
+    pub close_on_event: bool,
+    // sent when back closes the modal
+    pub cancel_event: Option<Event>
+}
+
+// modals built here don't listen to the store
+fn no_update(_objects: &mut Vec<Box<dyn Gui + Send>>, _state: &[u8], _canvas: &mut Canvas) {
+}
+
+#[allow(dead_code)]
+impl Modal {
+    pub fn new(view: View) -> Modal {
+        Modal {
+            view,
+            close_on_event: true,
+            cancel_event: None
+        }
+    }
+
+    // keep the modal open after events, close it with WindowViewer::pop_modal
+    pub fn keep_open(mut self) -> Modal {
+        self.close_on_event = false;
+        self
+    }
+
+    pub fn with_cancel_event(mut self, cancel_event: Event) -> Modal {
+        self.cancel_event = Some(cancel_event);
+        self
+    }
+
+    // an empty view for building modals by hand
+    pub fn view() -> View {
+        let (_sender, receiver) = channel();
+        View::new(no_update, receiver)
+    }
+
+    // "Are you sure?" prompt
+    // a panel with the message over two buttons, back sends the cancel event
+    #[allow(clippy::too_many_arguments)]
+    pub fn confirm(message: String, confirm_text: String, cancel_text: String, confirm_event: Event, cancel_event: Event, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> Modal {
+        let mut view = Modal::view();
+        let padding = config.padding as i32;
+        let spacing = config.spacing;
+        let button_h = config.font_size as i32 + 4 * padding;
+        let button_w = (w - 3 * spacing) / 2;
+        let button_y = y + h - spacing - button_h;
+
+        let panel = Block::new(x, y, w, h, Event::new("Modal Panel", None), config)
+            .with_style(Style::new().border(1).radius(4));
+        view.add_static_object(Box::new(panel));
+        let text = TextBlock::new(message, x + spacing, y + spacing, w - 2 * spacing, button_y - y - 2 * spacing, Event::new("Modal Message", None), config);
+        view.add_static_object(Box::new(text));
+
+        let confirm = Button::new(confirm_text, x + spacing, button_y, button_w, button_h, 0, 2 * padding, 0, confirm_event, config);
+        let cancel = Button::new(cancel_text, x + 2 * spacing + button_w, button_y, button_w, button_h, 0, 2 * padding, 0, cancel_event.clone(), config);
+        view.add_object(Box::new(confirm), 0, 0);
+        view.add_object(Box::new(cancel), 0, 1);
+        Modal::new(view).with_cancel_event(cancel_event)
+    }
+
+    // single button notice
+    #[allow(clippy::too_many_arguments)]
+    pub fn alert(message: String, ok_text: String, ok_event: Event, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> Modal {
+        let mut view = Modal::view();
+        let padding = config.padding as i32;
+        let spacing = config.spacing;
+        let button_h = config.font_size as i32 + 4 * padding;
+        let button_y = y + h - spacing - button_h;
+
+        let panel = Block::new(x, y, w, h, Event::new("Modal Panel", None), config)
+            .with_style(Style::new().border(1).radius(4));
+        view.add_static_object(Box::new(panel));
+        let text = TextBlock::new(message, x + spacing, y + spacing, w - 2 * spacing, button_y - y - 2 * spacing, Event::new("Modal Message", None), config);
+        view.add_static_object(Box::new(text));
+
+        let ok = Button::new(ok_text, x + spacing, button_y, w - 2 * spacing, button_h, 0, 2 * padding, config.font_size as i32, ok_event.clone(), config);
+        view.add_object(Box::new(ok), 0, 0);
+        Modal::new(view).with_cancel_event(ok_event)
+    }
+}
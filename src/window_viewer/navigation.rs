@@ -0,0 +1,193 @@
+use super::*;
+use super::super::store::Store;
+
+// Event / store Action names the WindowViewer navigates on
+// the first value is the view, by name or index
+pub const SHOW_VIEW: &str = "[Show View]";
+pub const PUSH_VIEW: &str = "[Push View]";
+pub const BACK_VIEW: &str = "[Back View]";
+pub const HOME_VIEW: &str = "[Home View]";
+
+// Navigation
+// views can be named and switched to by name, from code or from anywhere that sends
+// an Event or store Action, without reaching into the WindowViewer
+//
+//  window_viewer.add_named_view("settings", settings_view);
+//  let button = Button::new(..., Navigation::Push("settings".to_string()).event(), config);
+//  // or from a Dispatch handler, once forward_navigation has registered the names with the store
+//  Some(vec![Navigation::Show("home".to_string()).action()])
+#[derive(Clone, Debug, PartialEq)]
+pub enum Navigation {
+    // jump to a view, see set_active_view
+    Show(String),
+    // go to a view, back returns, see push_view
+    Push(String),
+    Back,
+    Home
+}
+
+impl Navigation {
+    // the navigation an Event or Action stands for, None if it isn't one
+    pub fn from_request(name: &str, values: &Option<Vec<String>>) -> Option<Navigation> {
+        let view = values.as_ref().and_then(|values| values.first()).cloned();
+        match (name, view) {
+            (SHOW_VIEW, Some(view)) => Some(Navigation::Show(view)),
+            (PUSH_VIEW, Some(view)) => Some(Navigation::Push(view)),
+            (BACK_VIEW, _) => Some(Navigation::Back),
+            (HOME_VIEW, _) => Some(Navigation::Home),
+            _ => None
+        }
+    }
+
+    fn request(&self) -> (&'static str, Option<Vec<String>>) {
+        match self {
+            Navigation::Show(view) => (SHOW_VIEW, Some(vec![view.clone()])),
+            Navigation::Push(view) => (PUSH_VIEW, Some(vec![view.clone()])),
+            Navigation::Back => (BACK_VIEW, None),
+            Navigation::Home => (HOME_VIEW, None)
+        }
+    }
+
+    pub fn event(&self) -> Event {
+        let (name, values) = self.request();
+        Event::new(name, values)
+    }
+
+    pub fn action(&self) -> StoreAction {
+        let (name, values) = self.request();
+        StoreAction::new(name, values)
+    }
+}
+
+// view among the names of every view, see WindowViewer::view_index
+fn find_view(names: &[Option<&str>], view: &str) -> Option<usize> {
+    match names.iter().position(|name| *name == Some(view)) {
+        Some(index) => Some(index),
+        None => view.parse::<usize>().ok().filter(|index| *index < names.len())
+    }
+}
+
+#[allow(dead_code)]
+impl WindowViewer {
+    pub fn add_named_view(&mut self, name: &'static str, view: View) {
+        if self.view_index(name).is_some() {
+            warn!("View name {} is already taken, the first view keeps it", name);
+        }
+        self.add_view(view.with_name(name));
+    }
+
+    // a name given to add_named_view, or failing that an index
+    pub fn view_index(&self, view: &str) -> Option<usize> {
+        let names: Vec<Option<&str>> = self.views.iter().map(|candidate| candidate.name).collect();
+        find_view(&names, view)
+    }
+
+    pub fn show_named_view(&mut self, name: &str) -> bool {
+        match self.view_index(name) {
+            Some(view) => {
+                self.switch_view(view, false);
+                true
+            },
+            None => {
+                warn!("Cannot show view {} which does not exist", name);
+                false
+            }
+        }
+    }
+
+    pub fn push_named_view(&mut self, name: &str) -> bool {
+        match self.view_index(name) {
+            Some(view) => self.push_view(view),
+            None => {
+                warn!("Cannot push view {} which does not exist", name);
+                false
+            }
+        }
+    }
+
+    pub fn navigate(&mut self, navigation: &Navigation) -> bool {
+        match navigation {
+            Navigation::Show(view) => self.show_named_view(view),
+            Navigation::Push(view) => self.push_named_view(view),
+            Navigation::Back => self.pop_view(),
+            Navigation::Home => {
+                self.go_home();
+                true
+            }
+        }
+    }
+
+    // send navigation Actions here, or use forward_navigation
+    pub fn get_navigation_sender(&self) -> Sender<StoreAction> {
+        self.navigation_sender.clone()
+    }
+
+    // have the store pass navigation Actions on to this WindowViewer
+    // they are still reduced as well, should the state want to know the current view
+    pub fn forward_navigation(&self, store: &mut Store) {
+        for name in &[SHOW_VIEW, PUSH_VIEW, BACK_VIEW, HOME_VIEW] {
+            store.reg_action_forwarder(name, self.get_navigation_sender());
+        }
+    }
+
+    // navigation Actions that came in since the last loop, true if the view changed
+    pub fn update_navigation(&mut self) -> bool {
+        let mut navigated = false;
+        while let Ok(action) = self.navigation_receiver.try_recv() {
+            if let Some(navigation) = Navigation::from_request(action.name, &action.values) {
+                navigated |= self.navigate(&navigation);
+            }
+        }
+        navigated
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    // see WindowViewer::add_named_view
+    pub fn with_name(mut self, name: &'static str) -> View {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn navigations() -> Vec<Navigation> {
+        vec![Navigation::Show("settings".to_string()), Navigation::Push("2".to_string()), Navigation::Back, Navigation::Home]
+    }
+
+    #[test]
+    fn navigation_round_trips_through_events_and_actions() {
+        for navigation in navigations() {
+            let event = navigation.event();
+            assert_eq!(Navigation::from_request(event.name, &event.values), Some(navigation.clone()));
+            let action = navigation.action();
+            assert_eq!(Navigation::from_request(action.name, &action.values), Some(navigation));
+        }
+    }
+
+    #[test]
+    fn show_and_push_need_a_view() {
+        assert_eq!(Navigation::from_request(SHOW_VIEW, &None), None);
+        assert_eq!(Navigation::from_request(PUSH_VIEW, &Some(vec![])), None);
+        assert_eq!(Navigation::from_request(BACK_VIEW, &None), Some(Navigation::Back));
+        assert_eq!(Navigation::from_request("Save", &Some(vec!["1".to_string()])), None);
+    }
+
+    #[test]
+    fn names_win_over_indexes() {
+        let names = [Some("home"), None, Some("1")];
+        assert_eq!(find_view(&names, "1"), Some(2));
+        assert_eq!(find_view(&names, "home"), Some(0));
+        assert_eq!(find_view(&names, "0"), Some(0));
+        assert_eq!(find_view(&names, "3"), None);
+        assert_eq!(find_view(&names, "settings"), None);
+    }
+}
@@ -0,0 +1,251 @@
+use super::*;
+use super::super::canvas::{Layer, Draw, Rect, Viewport};
+use uuid::Uuid;
+
+// width of the scrollbar drawn along the viewport's right / bottom edge
+const SCROLLBAR_WIDTH: i32 = 4;
+
+// Scroll
+// lets a view's content run past the area it is shown in
+// objects keep their content coordinates, the canvas draws them through a Viewport
+// the selected object is scrolled into view whenever the selection moves
+//
+//  let view = View::new(updater, receiver)
+//      .with_scroll(0, 40, 320, 200)
+//      .with_scrollbar(config);
+pub struct Scroll {
+    viewport: Viewport,
+    // room left around the selected object when scrolling to it
+    margin: i32,
+    scrollbar: Option<&'static GuiConfig>,
+    scrollbar_name: String,
+    // the canvas has not seen the latest offset yet
+    changed: bool,
+    active: bool
+}
+
+impl Scroll {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Scroll {
+        Scroll {
+            viewport: Viewport::new(x, y, w, h),
+            margin: 0,
+            scrollbar: None,
+            scrollbar_name: format!("Scrollbar - {}", Uuid::new_v4().to_hyphenated()),
+            changed: true,
+            active: false
+        }
+    }
+
+    pub fn offset(&self) -> (i32, i32) {
+        (self.viewport.scroll_x, self.viewport.scroll_y)
+    }
+
+    fn scroll_to(&mut self, scroll_x: i32, scroll_y: i32) {
+        if (scroll_x, scroll_y) != self.offset() {
+            self.viewport = self.viewport.scrolled(scroll_x, scroll_y);
+            self.changed = true;
+        }
+    }
+
+    // the smallest scroll along one axis that shows start..start + len
+    // view_start, view_len are the viewport along that axis, content_end where the content stops
+    fn follow(scroll: i32, start: i32, len: i32, view: (i32, i32), content_end: i32, margin: i32) -> i32 {
+        let (view_start, view_len) = view;
+        let mut scroll = scroll;
+        if start + len + margin > view_start + view_len + scroll {
+            scroll = start + len + margin - view_start - view_len;
+        }
+        if start - margin < view_start + scroll {
+            scroll = start - margin - view_start;
+        }
+        scroll.min(content_end - view_start - view_len).max(0)
+    }
+
+    fn gen_scrollbar(&self, content: (i32, i32), config: &'static GuiConfig) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let Viewport {x, y, w, h, scroll_x, scroll_y} = self.viewport;
+        let (content_w, content_h) = content;
+        let track = config.palette.background(&GuiState::Base);
+        let thumb = config.palette.outline(&GuiState::Selected);
+        let mut layers: Vec<Layer<Box<dyn Draw + Send>>> = vec![];
+        if content_h > h {
+            let bar_x = x + w - SCROLLBAR_WIDTH;
+            let thumb_h = (h * h / content_h).max(SCROLLBAR_WIDTH);
+            let thumb_y = y + scroll_y * (h - thumb_h) / (content_h - h);
+            layers.push(Layer::new(Box::new(Rect::new(bar_x, y, SCROLLBAR_WIDTH, h, true, track.clone())), self.active, self.scrollbar_name.clone()));
+            layers.push(Layer::new(Box::new(Rect::new(bar_x, thumb_y, SCROLLBAR_WIDTH, thumb_h, true, thumb.clone()).with_radius(SCROLLBAR_WIDTH / 2)), self.active, self.scrollbar_name.clone()));
+        }
+        if content_w > w {
+            let bar_y = y + h - SCROLLBAR_WIDTH;
+            let thumb_w = (w * w / content_w).max(SCROLLBAR_WIDTH);
+            let thumb_x = x + scroll_x * (w - thumb_w) / (content_w - w);
+            layers.push(Layer::new(Box::new(Rect::new(x, bar_y, w, SCROLLBAR_WIDTH, true, track)), self.active, self.scrollbar_name.clone()));
+            layers.push(Layer::new(Box::new(Rect::new(thumb_x, bar_y, thumb_w, SCROLLBAR_WIDTH, true, thumb).with_radius(SCROLLBAR_WIDTH / 2)), self.active, self.scrollbar_name.clone()));
+        }
+        layers
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    // show the view through an x, y, w, h window, content past it scrolls
+    pub fn with_scroll(mut self, x: i32, y: i32, w: i32, h: i32) -> View {
+        self.scroll = Some(Scroll::new(x, y, w, h));
+        self
+    }
+
+    // keep some of the neighbouring content in sight around the selection
+    pub fn with_scroll_margin(mut self, margin: i32) -> View {
+        if let Some(scroll) = self.scroll.as_mut() {
+            scroll.margin = margin;
+        }
+        self
+    }
+
+    // draw a bar along the viewport edge when the content does not fit
+    pub fn with_scrollbar(mut self, config: &'static GuiConfig) -> View {
+        if let Some(scroll) = self.scroll.as_mut() {
+            scroll.scrollbar = Some(config);
+        }
+        self
+    }
+
+    pub fn scroll_offset(&self) -> Option<(i32, i32)> {
+        self.scroll.as_ref().map(|scroll| scroll.offset())
+    }
+
+    // scroll to an offset, clamped to the content, the selection stays where it is
+    pub fn scroll_to(&mut self, scroll_x: i32, scroll_y: i32) {
+        let (content_w, content_h) = self.content_size();
+        if let Some(scroll) = self.scroll.as_mut() {
+            let Viewport {w, h, ..} = scroll.viewport;
+            scroll.scroll_to(
+                scroll_x.min(content_w - w).max(0),
+                scroll_y.min(content_h - h).max(0)
+            );
+        }
+    }
+
+    // right and bottom edge of the objects, measured from the viewport origin
+    fn content_size(&self) -> (i32, i32) {
+        let (origin_x, origin_y) = match &self.scroll {
+            Some(scroll) => (scroll.viewport.x, scroll.viewport.y),
+            None => (0, 0)
+        };
+        self.objects.iter()
+            .filter_map(|object| object.bounds())
+            .fold((0, 0), |(content_w, content_h), (x, y, w, h)| {
+                (content_w.max(x + w - origin_x), content_h.max(y + h - origin_y))
+            })
+    }
+
+    pub(super) fn scroll_to_selected(&mut self) {
+        if self.scroll.is_none() || self.objects.is_empty() {
+            return;
+        }
+        let bounds = match self.objects[self.selected_object].bounds() {
+            Some(bounds) => bounds,
+            None => return
+        };
+        let (content_w, content_h) = self.content_size();
+        if let Some(scroll) = self.scroll.as_mut() {
+            let (x, y, w, h) = bounds;
+            let Viewport {x: view_x, y: view_y, w: view_w, h: view_h, scroll_x, scroll_y} = scroll.viewport;
+            let scroll_x = Scroll::follow(scroll_x, x, w, (view_x, view_w), view_x + content_w, scroll.margin);
+            let scroll_y = Scroll::follow(scroll_y, y, h, (view_y, view_h), view_y + content_h, scroll.margin);
+            scroll.scroll_to(scroll_x, scroll_y);
+        }
+    }
+
+    // push a new offset to the canvas and redraw the scrollbar
+    // redrawn objects may have new layer groups, those are put behind the viewport too
+    pub(super) fn update_scroll(&mut self, redrawn: bool, canvas: &mut Canvas) -> bool {
+        let changed = match &self.scroll {
+            Some(scroll) => scroll.changed,
+            None => return false
+        };
+        if !changed && !redrawn {
+            return false;
+        }
+        let groups: Vec<String> = self.objects.iter().flat_map(|object| object.layer_groups()).collect();
+        let content = self.content_size();
+        if let Some(scroll) = self.scroll.as_mut() {
+            for group in groups {
+                canvas.set_viewport(group, scroll.viewport);
+            }
+            if let (true, Some(config)) = (changed, scroll.scrollbar) {
+                canvas.replace_layer_group(scroll.scrollbar_name.clone(), scroll.gen_scrollbar(content, config));
+            }
+            scroll.changed = false;
+        }
+        changed
+    }
+
+    pub(super) fn activate_scroll(&mut self, canvas: &mut Canvas) {
+        if let Some(scroll) = self.scroll.as_mut() {
+            scroll.active = true;
+            // objects may have added layer groups since the last scroll
+            scroll.changed = true;
+            canvas.activate_layer_group(scroll.scrollbar_name.clone());
+        }
+    }
+
+    pub(super) fn deactivate_scroll(&mut self, canvas: &mut Canvas) {
+        if let Some(scroll) = self.scroll.as_mut() {
+            scroll.active = false;
+            canvas.deactivate_layer_group(scroll.scrollbar_name.clone());
+        }
+    }
+
+    pub(super) fn scroll_layer_groups(&self) -> Vec<String> {
+        match &self.scroll {
+            Some(scroll) if scroll.scrollbar.is_some() => vec![scroll.scrollbar_name.clone()],
+            _ => vec![]
+        }
+    }
+
+    pub(super) fn set_scroll_config(&mut self, config: &'static GuiConfig) {
+        if let Some(scroll) = self.scroll.as_mut() {
+            if scroll.scrollbar.is_some() {
+                scroll.scrollbar = Some(config);
+                scroll.changed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 200 long viewport from 100, over content running to 700, with a 10 margin
+    fn follow(scroll: i32, start: i32, len: i32) -> i32 {
+        Scroll::follow(scroll, start, len, (100, 200), 700, 10)
+    }
+
+    #[test]
+    fn scrolls_forward_to_show_the_end() {
+        assert_eq!(follow(0, 350, 40), 100);
+    }
+
+    #[test]
+    fn scrolls_back_to_show_the_start() {
+        assert_eq!(follow(300, 150, 40), 40);
+    }
+
+    #[test]
+    fn visible_objects_do_not_scroll() {
+        assert_eq!(follow(100, 250, 40), 100);
+    }
+
+    #[test]
+    fn stops_at_the_content_edges() {
+        assert_eq!(follow(0, 660, 40), 400);
+        assert_eq!(follow(50, 100, 40), 0);
+    }
+
+    #[test]
+    fn short_content_never_scrolls() {
+        assert_eq!(Scroll::follow(50, 240, 40, (100, 200), 250, 10), 0);
+        assert_eq!(Scroll::follow(0, 280, 40, (100, 200), 250, 10), 0);
+    }
+}
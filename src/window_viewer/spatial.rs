@@ -0,0 +1,192 @@
+use super::*;
+use serde::{Serialize, Deserialize};
+
+// how the d-pad moves the selection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavMode {
+    // row / column / cell of the nav_index
+    #[default]
+    Grid,
+    // closest object on screen in the pressed direction, by bounds
+    Spatial
+}
+
+// how far off the pressed direction a candidate may be before it loses to one further along it
+const ORTHOGONAL_WEIGHT: i32 = 2;
+
+// distance score of candidate from current moving in (dx, dy), None if it is not that way
+// along is edge to edge in the direction, across is how far the centers are apart sideways
+fn spatial_score(current: (i32, i32, i32, i32), candidate: (i32, i32, i32, i32), dx: isize, dy: isize) -> Option<i32> {
+    let (x, y, w, h) = current;
+    let (cx, cy, cw, ch) = candidate;
+    let (center_x, center_y) = (x + w / 2, y + h / 2);
+    let (candidate_x, candidate_y) = (cx + cw / 2, cy + ch / 2);
+    let (along, across) = match (dx.signum(), dy.signum()) {
+        (1, _) if candidate_x > center_x => (cx - (x + w), candidate_y - center_y),
+        (-1, _) if candidate_x < center_x => (x - (cx + cw), candidate_y - center_y),
+        (_, 1) if candidate_y > center_y => (cy - (y + h), candidate_x - center_x),
+        (_, -1) if candidate_y < center_y => (y - (cy + ch), candidate_x - center_x),
+        _ => return None
+    };
+    Some(along.max(0) + ORTHOGONAL_WEIGHT * across.abs())
+}
+
+#[allow(dead_code)]
+impl View {
+    pub fn with_nav_mode(mut self, nav_mode: NavMode) -> View {
+        self.nav_mode = nav_mode;
+        self
+    }
+
+    pub fn set_nav_mode(&mut self, nav_mode: NavMode) {
+        self.nav_mode = nav_mode;
+    }
+
+    // a navigable object for views that only navigate spatially
+    // it shares nav cell (0, 0), so grid navigation walks them in the order they were added
+    pub fn add_focusable_object(&mut self, object: Box<dyn Gui + Send>) {
+        self.add_object(object, 0, 0);
+    }
+
+    // move to the closest navigable object in the direction (dx, dy)
+    // returns false when there is nothing that way, or the selection has no bounds
+    pub fn spatial_move(&mut self, dx: isize, dy: isize) -> bool {
+        if self.objects.is_empty() {
+            return false;
+        }
+        let current = match self.objects[self.selected_object].bounds() {
+            Some(bounds) => bounds,
+            None => return false
+        };
+        match self.spatial_candidate(current, dx, dy) {
+            Some(object) => self.select_object(object),
+            None => false
+        }
+    }
+
+    // carry on from the far side, as if coming in from beyond the opposite edge
+    pub(super) fn spatial_wrap(&mut self, dx: isize, dy: isize) -> bool {
+        if self.objects.is_empty() {
+            return false;
+        }
+        let (x, y, w, h) = match self.objects[self.selected_object].bounds() {
+            Some(bounds) => bounds,
+            None => return false
+        };
+        let all: Vec<(i32, i32, i32, i32)> = self.navigable_objects().iter()
+            .filter_map(|object| self.objects[*object].bounds())
+            .collect();
+        let left = all.iter().map(|b| b.0).min().unwrap_or(x);
+        let top = all.iter().map(|b| b.1).min().unwrap_or(y);
+        let right = all.iter().map(|b| b.0 + b.2).max().unwrap_or(x + w);
+        let bottom = all.iter().map(|b| b.1 + b.3).max().unwrap_or(y + h);
+        let from = match (dx.signum(), dy.signum()) {
+            (1, _) => (left - w - 1, y, w, h),
+            (-1, _) => (right + 1, y, w, h),
+            (_, 1) => (x, top - h - 1, w, h),
+            (_, -1) => (x, bottom + 1, w, h),
+            _ => return false
+        };
+        match self.spatial_candidate(from, dx, dy) {
+            Some(object) => self.select_object(object),
+            None => false
+        }
+    }
+
+    // closest navigable object other than the selected one, moving from bounds in (dx, dy)
+    fn spatial_candidate(&self, from: (i32, i32, i32, i32), dx: isize, dy: isize) -> Option<usize> {
+        let mut best: Option<(i32, usize)> = None;
+        for object in self.navigable_objects() {
+            if object == self.selected_object {
+                continue;
+            }
+            let score = self.objects[object].bounds()
+                .and_then(|candidate| spatial_score(from, candidate, dx, dy));
+            match (score, best) {
+                (Some(score), Some((best_score, _))) if score >= best_score => (),
+                (Some(score), _) => best = Some((score, object)),
+                (None, _) => ()
+            }
+        }
+        best.map(|(_, object)| object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util::*;
+
+    #[test]
+    fn candidates_behind_the_direction_have_no_score() {
+        let current = (100, 100, 20, 20);
+        assert!(spatial_score(current, (50, 100, 20, 20), 1, 0).is_none());
+        assert!(spatial_score(current, (150, 100, 20, 20), -1, 0).is_none());
+        assert!(spatial_score(current, (100, 50, 20, 20), 0, 1).is_none());
+        assert!(spatial_score(current, (100, 150, 20, 20), 0, -1).is_none());
+    }
+
+    #[test]
+    fn straight_ahead_beats_closer_but_off_axis() {
+        let current = (0, 0, 20, 20);
+        let ahead = spatial_score(current, (60, 0, 20, 20), 1, 0).unwrap();
+        let diagonal = spatial_score(current, (30, 40, 20, 20), 1, 0).unwrap();
+        assert!(ahead < diagonal);
+    }
+
+    #[test]
+    fn overlapping_candidates_count_as_touching() {
+        assert_eq!(spatial_score((0, 0, 40, 20), (30, 0, 40, 20), 1, 0), Some(0));
+    }
+
+    fn spatial_view(boxes: &[(i32, i32, i32, i32)]) -> View {
+        let mut view = view().with_nav_mode(NavMode::Spatial);
+        for (x, y, w, h) in boxes {
+            view.add_focusable_object(Placeholder::boxed(*x, *y, *w, *h));
+        }
+        view
+    }
+
+    #[test]
+    fn moves_to_the_closest_object_that_way() {
+        //  0   1
+        //  2     3
+        let mut view = spatial_view(&[(0, 0, 20, 20), (40, 0, 20, 20), (0, 40, 20, 20), (60, 40, 20, 20)]);
+        view.select_object(0);
+        assert!(view.spatial_move(1, 0));
+        assert_eq!(view.selected_object, 1);
+        assert!(view.spatial_move(0, 1));
+        assert_eq!(view.selected_object, 3);
+        assert!(view.spatial_move(-1, 0));
+        assert_eq!(view.selected_object, 2);
+    }
+
+    #[test]
+    fn nothing_that_way_stays_put() {
+        let mut view = spatial_view(&[(0, 0, 20, 20), (40, 0, 20, 20)]);
+        view.select_object(0);
+        assert!(!view.spatial_move(-1, 0));
+        assert!(!view.spatial_move(0, 1));
+        assert_eq!(view.selected_object, 0);
+    }
+
+    #[test]
+    fn wraps_to_the_far_side() {
+        let mut view = spatial_view(&[(0, 0, 20, 20), (40, 0, 20, 20), (80, 0, 20, 20)]);
+        assert!(view.select_object(2));
+        assert!(view.spatial_wrap(1, 0));
+        assert_eq!(view.selected_object, 0);
+        assert!(view.spatial_wrap(-1, 0));
+        assert_eq!(view.selected_object, 2);
+    }
+
+    #[test]
+    fn disabled_objects_are_skipped() {
+        let mut view = spatial_view(&[(0, 0, 20, 20), (40, 0, 20, 20), (80, 0, 20, 20)]);
+        view.gui_state[1] = GuiState::Disabled;
+        view.select_object(0);
+        assert!(view.spatial_move(1, 0));
+        assert_eq!(view.selected_object, 2);
+    }
+}
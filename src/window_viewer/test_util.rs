@@ -0,0 +1,41 @@
+use super::*;
+use super::super::fb::Color;
+use std::sync::mpsc::channel;
+
+// a widget with bounds and nothing to draw, for exercising views without a framebuffer
+pub struct Placeholder {
+    bounds: Option<(i32, i32, i32, i32)>
+}
+
+impl Placeholder {
+    pub fn boxed(x: i32, y: i32, w: i32, h: i32) -> Box<dyn Gui + Send> {
+        Box::new(Placeholder {bounds: Some((x, y, w, h))})
+    }
+}
+
+impl Gui for Placeholder {
+    fn bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        self.bounds
+    }
+}
+
+// one config for every test that needs a real widget
+pub fn config() -> &'static GuiConfig {
+    GuiConfig::cached("test", || {
+        Ok(GuiConfig::new(Palette::from_accent(Color::from_hsl(210.0, 0.6, 0.5)), "assets/fonts/Nanum_Gothic/NanumGothic-Regular.ttf"))
+    }).unwrap()
+}
+
+pub fn view() -> View {
+    let (_, receiver) = channel();
+    View::new(|_, _, _| (), receiver)
+}
+
+// a view with a placeholder 20 x 20 at each (row, column), spaced 30 apart
+pub fn grid_view(cells: &[(usize, usize)]) -> View {
+    let mut view = view();
+    for (row, column) in cells {
+        view.add_object(Placeholder::boxed(*column as i32 * 30, *row as i32 * 30, 20, 20), *row, *column);
+    }
+    view
+}
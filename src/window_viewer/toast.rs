@@ -0,0 +1,203 @@
+use super::*;
+use super::super::canvas::{Layer, Draw, Rect, Text};
+use super::super::fb::Color;
+use std::collections::VecDeque;
+use std::time::Instant;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error
+}
+
+impl Severity {
+    pub fn background(&self) -> Color {
+        match self {
+            Severity::Info => Color::new(0x37, 0x47, 0x4f),
+            Severity::Success => Color::new(0x2e, 0x7d, 0x32),
+            Severity::Warning => Color::new(0xf9, 0xa8, 0x25),
+            Severity::Error => Color::new(0xc6, 0x28, 0x28)
+        }
+    }
+}
+
+// Toast
+// short message shown over everything, dismissed after duration
+#[derive(Clone, Debug)]
+pub struct Toast {
+    pub message: String,
+    pub severity: Severity,
+    pub duration: Duration
+}
+
+impl Toast {
+    pub fn new(message: String, severity: Severity) -> Toast {
+        let duration = match severity {
+            Severity::Error => Duration::from_secs(5),
+            _ => Duration::from_secs(2)
+        };
+        Toast {message, severity, duration}
+    }
+
+    pub fn with_duration(mut self, duration: Duration) -> Toast {
+        self.duration = duration;
+        self
+    }
+}
+
+// maps dispatched events to toasts, e.g. "Save" -> "Saved"
+pub type EventNotifier = fn(&Event) -> Option<Toast>;
+
+// Toasts
+// queue of toasts, one on screen at a time
+#[derive(Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+    // group name and when the toast on screen goes away
+    showing: Option<(String, Instant)>,
+    config: Option<&'static GuiConfig>
+}
+
+impl Toasts {
+    pub fn new() -> Toasts {
+        Toasts::default()
+    }
+
+    pub fn set_config(&mut self, config: &'static GuiConfig) {
+        self.config = Some(config);
+    }
+
+    // draw with the same config as view, unless one was set
+    pub fn default_config_from(&mut self, view: &View) {
+        if self.config.is_none() {
+            self.config = view.config();
+        }
+    }
+
+    pub fn push(&mut self, toast: Toast) {
+        self.queue.push_back(toast);
+    }
+
+    // drop everything, including the toast on screen
+    pub fn clear(&mut self, canvas: &mut Canvas) -> bool {
+        self.queue.clear();
+        match self.showing.take() {
+            Some((group, _)) => {
+                canvas.drop_layer_group(group);
+                true
+            },
+            None => false
+        }
+    }
+
+    // when the toast on screen has to go
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.showing.as_ref().map(|(_, until)| *until)
+    }
+
+    // dismiss expired toasts and show the next one
+    // raise keeps the toast above anything redrawn since the last call
+    pub fn update(&mut self, canvas: &mut Canvas, raise: bool) -> bool {
+        let now = Instant::now();
+        let mut changed = false;
+        match self.expire(now) {
+            Some(group) => {
+                canvas.drop_layer_group(group);
+                changed = true;
+            },
+            None => if let (Some((group, _)), true) = (&self.showing, raise) {
+                canvas.raise_layer_group(group.clone());
+            }
+        }
+        if let Some((toast, group, config)) = self.show_next(now) {
+            canvas.layers.append(&mut Toasts::gen_layers(&toast, &group, config, canvas.size()));
+            changed = true;
+        }
+        changed
+    }
+
+    // the group of the toast on screen, once it is due to go
+    fn expire(&mut self, now: Instant) -> Option<String> {
+        match &self.showing {
+            Some((_, until)) if now >= *until => self.showing.take().map(|(group, _)| group),
+            _ => None
+        }
+    }
+
+    // the next toast and its group, when nothing is on screen
+    // toasts wait in the queue until there is a config to draw them with
+    fn show_next(&mut self, now: Instant) -> Option<(Toast, String, &'static GuiConfig)> {
+        if self.showing.is_some() {
+            return None;
+        }
+        let config = self.config?;
+        let toast = self.queue.pop_front()?;
+        let group = format!("Toast - {}", Uuid::new_v4().to_hyphenated());
+        self.showing = Some((group.clone(), now + toast.duration));
+        Some((toast, group, config))
+    }
+
+    // rounded box along the bottom of the screen
+    fn gen_layers(toast: &Toast, group: &str, config: &'static GuiConfig, size: (i32, i32)) -> Vec<Layer<Box<dyn Draw + Send>>> {
+        let (screen_w, screen_h) = size;
+        let padding = config.padding as i32 * 2;
+        let spacing = config.spacing;
+        let background = toast.severity.background();
+        let mut text = Text::new(0, 0, config.font_size, toast.message.clone(), &config.font, background.contrasting_text(), config.padding);
+        let w = (text.w + 2 * padding).min(screen_w - 2 * spacing);
+        let h = text.h + 2 * padding;
+        let x = (screen_w - w) / 2;
+        let y = screen_h - h - spacing * 2;
+        text.x = x + padding;
+        text.y = y + padding;
+        let panel = Rect::new(x, y, w, h, true, background).with_radius(h / 4);
+        vec![
+            Layer::new(Box::new(panel), true, group.to_string()),
+            Layer::new(Box::new(text), true, group.to_string())
+        ]
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    // the config the view's objects draw with, the first one found
+    pub fn config(&self) -> Option<&'static GuiConfig> {
+        self.objects.iter().find_map(|object| object.config())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util;
+    use super::super::super::gui_tk::Spinner;
+
+    #[test]
+    fn toasts_use_the_view_config_and_expire() {
+        let mut view = test_util::view();
+        view.add_object(Box::new(Spinner::new(0.0, 0.0, 10.0, 1.0, 0, 0, 100, 40, Event::new("Set", None), test_util::config())), 0, 0);
+        let mut toasts = Toasts::new();
+        toasts.default_config_from(&view);
+        toasts.push(Toast::new("Saved".to_string(), Severity::Success));
+        let now = Instant::now();
+        let (toast, group, _) = toasts.show_next(now).unwrap();
+        assert_eq!(toast.message, "Saved");
+        assert_eq!(toasts.next_deadline(), Some(now + toast.duration));
+        assert!(toasts.expire(now).is_none());
+        assert_eq!(toasts.expire(now + toast.duration), Some(group));
+        assert!(toasts.next_deadline().is_none());
+    }
+
+    #[test]
+    fn toasts_wait_for_a_config() {
+        let mut toasts = Toasts::new();
+        toasts.default_config_from(&test_util::view());
+        toasts.push(Toast::new("Saved".to_string(), Severity::Info));
+        assert!(toasts.show_next(Instant::now()).is_none());
+        toasts.set_config(test_util::config());
+        assert!(toasts.show_next(Instant::now()).is_some());
+    }
+}
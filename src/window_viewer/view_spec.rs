@@ -0,0 +1,483 @@
+use super::*;
+use super::super::gui_tk::{
+    Block, Button, TextBlock, GuiImage, Spinner, Toggle, Checkbox, ProgressBar,
+    Slider, Chart, Gauge, Keyboard, Menu, MenuItem, Style, Orientation
+};
+use serde::{Serialize, Deserialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+
+// ViewSpec
+// a view described in a toml or json file, so the ui can change without a rebuild
+// widgets with a row (and column) are navigable, the rest are static
+// layouts (hstack, vstack, grid) place their children and fill the nav_index themselves
+// bind names a state key, the view's StateBinder resolves it on every update
+//
+//  viewport = [0, 40, 320, 200]
+//  scrollbar = true
+//  nav_mode = "spatial"
+//  wrap_vertical = true
+//
+//  [[widgets]]
+//  type = "button"
+//  text = "Start"
+//  event = "Start"
+//  x = 10
+//  y = 40
+//  w = 100
+//  h = 30
+//  row = 0
+//  column = 0
+//
+//  [[widgets]]
+//  type = "vstack"
+//  y = 80
+//  w = 320
+//  h = 160
+//  spacing = 4
+//  children = [
+//      {type = "progress_bar", bind = "battery", static_widget = true},
+//      {type = "slider", event = "Volume", bind = "volume", max = 11.0}
+//  ]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ViewSpec {
+    // x, y, w, h the view scrolls within, see View::with_scroll
+    pub viewport: Option<(i32, i32, i32, i32)>,
+    #[serde(default)]
+    pub scrollbar: bool,
+    #[serde(default)]
+    pub nav_mode: NavMode,
+    #[serde(default)]
+    pub wrap_horizontal: bool,
+    #[serde(default)]
+    pub wrap_vertical: bool,
+    #[serde(default)]
+    pub widgets: Vec<WidgetSpec>
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WidgetSpec {
+    #[serde(flatten)]
+    pub kind: WidgetKind,
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub w: i32,
+    #[serde(default)]
+    pub h: i32,
+    // nav_index cell, ignored inside layouts
+    pub row: Option<usize>,
+    pub column: Option<usize>,
+    pub event: Option<String>,
+    pub bind: Option<String>,
+    pub style: Option<Style>,
+    // inside layouts
+    pub size: Option<Size>,
+    pub cross_size: Option<i32>,
+    #[serde(default)]
+    pub static_widget: bool
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WidgetKind {
+    Button {
+        text: String,
+        #[serde(default)]
+        text_x: i32,
+        text_y: Option<i32>,
+        text_h: Option<i32>
+    },
+    Block,
+    TextBlock {
+        text: String
+    },
+    Image {
+        path: String,
+        alternate: Option<String>
+    },
+    Spinner {
+        #[serde(default)]
+        value: f64,
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "default_max")]
+        max: f64,
+        #[serde(default = "default_step")]
+        step: f64,
+        #[serde(default)]
+        precision: usize,
+        #[serde(default)]
+        unit: String
+    },
+    Toggle {
+        #[serde(default)]
+        checked: bool
+    },
+    Checkbox {
+        #[serde(default)]
+        checked: bool,
+        text: String
+    },
+    ProgressBar {
+        #[serde(default)]
+        value: f64,
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "default_max")]
+        max: f64,
+        #[serde(default = "default_orientation")]
+        orientation: Orientation,
+        #[serde(default)]
+        precision: usize,
+        #[serde(default)]
+        unit: String
+    },
+    Slider {
+        #[serde(default)]
+        value: f64,
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "default_max")]
+        max: f64,
+        #[serde(default = "default_step")]
+        step: f64,
+        #[serde(default = "default_orientation")]
+        orientation: Orientation,
+        #[serde(default)]
+        precision: usize
+    },
+    Chart {
+        series: Vec<String>,
+        #[serde(default = "default_capacity")]
+        capacity: usize,
+        y_range: Option<(f64, f64)>,
+        // rows, columns
+        grid: Option<(usize, usize)>
+    },
+    Gauge {
+        #[serde(default)]
+        value: f64,
+        #[serde(default)]
+        min: f64,
+        #[serde(default = "default_max")]
+        max: f64,
+        #[serde(default)]
+        precision: usize,
+        #[serde(default)]
+        unit: String
+    },
+    Keyboard {
+        #[serde(default)]
+        text: String,
+        max_length: Option<usize>
+    },
+    Menu {
+        items: Vec<MenuItemSpec>
+    },
+    Hstack(StackSpec),
+    Vstack(StackSpec),
+    Grid(StackSpec)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MenuItemSpec {
+    pub text: String,
+    pub event: String,
+    pub name: Option<String>
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StackSpec {
+    #[serde(default)]
+    pub spacing: i32,
+    #[serde(default)]
+    pub padding: i32,
+    #[serde(default = "default_align")]
+    pub align: Align,
+    // grids only
+    #[serde(default = "default_columns")]
+    pub columns: usize,
+    #[serde(default)]
+    pub children: Vec<WidgetSpec>
+}
+
+fn default_max() -> f64 {
+    100.0
+}
+
+fn default_step() -> f64 {
+    1.0
+}
+
+fn default_orientation() -> Orientation {
+    Orientation::Horizontal
+}
+
+fn default_capacity() -> usize {
+    100
+}
+
+fn default_align() -> Align {
+    Align::Center
+}
+
+fn default_columns() -> usize {
+    1
+}
+
+// widgets and events hold &'static str
+// names loaded at runtime are leaked to live as long as the app, like GuiConfig::into_static
+fn leak(text: &str) -> &'static str {
+    Box::leak(text.to_string().into_boxed_str())
+}
+
+impl ViewSpec {
+    // .json files are read as json, anything else as toml
+    pub fn from_file(path: &str) -> Result<ViewSpec, Box<dyn Error>> {
+        let mut file = File::open(path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("json") => ViewSpec::from_json(&contents),
+            _ => ViewSpec::from_toml(&contents)
+        }
+    }
+
+    pub fn from_toml(contents: &str) -> Result<ViewSpec, Box<dyn Error>> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn from_json(contents: &str) -> Result<ViewSpec, Box<dyn Error>> {
+        Ok(serde_json::from_str(contents)?)
+    }
+
+    pub fn build(self, update_fn: ViewStateUpdater, state_receiver: Receiver<Vec<u8>>, config: &'static GuiConfig) -> Result<View, Box<dyn Error>> {
+        let mut view = View::new(update_fn, state_receiver)
+            .with_nav_mode(self.nav_mode)
+            .with_wrap(self.wrap_horizontal, self.wrap_vertical);
+        if let Some((x, y, w, h)) = self.viewport {
+            view = view.with_scroll(x, y, w, h);
+            if self.scrollbar {
+                view = view.with_scrollbar(config);
+            }
+        }
+        self.build_into(&mut view, config)?;
+        Ok(view)
+    }
+
+    // add the described widgets to an existing view
+    pub fn build_into(self, view: &mut View, config: &'static GuiConfig) -> Result<(), Box<dyn Error>> {
+        self.check()?;
+        for spec in self.widgets {
+            spec.add_to(view, config);
+        }
+        Ok(())
+    }
+
+    // what the widgets would panic on while building, e.g. an image file that is missing or won't decode
+    pub fn check(&self) -> Result<(), Box<dyn Error>> {
+        self.widgets.iter().try_for_each(WidgetSpec::check)
+    }
+}
+
+impl WidgetSpec {
+    fn check(&self) -> Result<(), Box<dyn Error>> {
+        match &self.kind {
+            WidgetKind::Image {path, alternate} => {
+                for path in Some(path).into_iter().chain(alternate) {
+                    GuiImage::load(path)?;
+                }
+                Ok(())
+            },
+            WidgetKind::Hstack(stack) | WidgetKind::Vstack(stack) | WidgetKind::Grid(stack) => {
+                stack.children.iter().try_for_each(WidgetSpec::check)
+            },
+            _ => Ok(())
+        }
+    }
+
+    fn is_layout(&self) -> bool {
+        matches!(self.kind, WidgetKind::Hstack(_) | WidgetKind::Vstack(_) | WidgetKind::Grid(_))
+    }
+
+    fn event(&self) -> Event {
+        Event::new(self.event.as_deref().map(leak).unwrap_or(""), None)
+    }
+
+    fn add_to(self, view: &mut View, config: &'static GuiConfig) {
+        let (x, y, w, h) = (self.x, self.y, self.w, self.h);
+        if self.is_layout() {
+            match self.row {
+                Some(row) => {
+                    let column = self.column.unwrap_or(0);
+                    self.into_layout(config).apply_at(view, row, column, x, y, w, h);
+                },
+                None => view.add_layout(self.into_layout(config), x, y, w, h)
+            }
+            return;
+        }
+        let bind = self.bind.as_deref().map(leak);
+        let nav = self.row.map(|row| (row, self.column.unwrap_or(0)));
+        let object = self.into_widget(x, y, w, h, config);
+        match nav {
+            Some((row, column)) => view.add_object(object, row, column),
+            None => view.add_static_object(object)
+        }
+        if let Some(key) = bind {
+            view.bind(view.objects_len() - 1, key);
+        }
+    }
+
+    fn into_child(self, config: &'static GuiConfig) -> LayoutChild {
+        let size = self.size;
+        let cross_size = self.cross_size;
+        let bind = self.bind.as_deref().map(leak);
+        let mut child = if self.is_layout() {
+            LayoutChild::layout(self.into_layout(config))
+        } else if self.static_widget {
+            LayoutChild::static_widget(move |x, y, w, h| self.into_widget(x, y, w, h, config))
+        } else {
+            LayoutChild::widget(move |x, y, w, h| self.into_widget(x, y, w, h, config))
+        };
+        if let Some(size) = size {
+            child = child.size(size);
+        }
+        if let Some(cross_size) = cross_size {
+            child = child.cross_size(cross_size);
+        }
+        if let Some(key) = bind {
+            child = child.bind(key);
+        }
+        child
+    }
+
+    fn into_layout(self, config: &'static GuiConfig) -> Layout {
+        let (layout, stack) = match self.kind {
+            WidgetKind::Hstack(stack) => (Layout::hstack(), stack),
+            WidgetKind::Vstack(stack) => (Layout::vstack(), stack),
+            WidgetKind::Grid(stack) => (Layout::grid(stack.columns), stack),
+            _ => unreachable!("only stacks and grids are layouts")
+        };
+        let mut layout = layout.spacing(stack.spacing).padding(stack.padding).align(stack.align);
+        for child in stack.children {
+            layout = layout.child(child.into_child(config));
+        }
+        layout
+    }
+
+    fn into_widget(self, x: i32, y: i32, w: i32, h: i32, config: &'static GuiConfig) -> Box<dyn Gui + Send> {
+        let event = self.event();
+        let style = self.style.unwrap_or_default();
+        match self.kind {
+            WidgetKind::Button {text, text_x, text_y, text_h} => {
+                let text_y = text_y.unwrap_or(2 * config.padding as i32);
+                let text_h = text_h.unwrap_or(0);
+                Box::new(Button::new(text, x, y, w, h, text_x, text_y, text_h, event, config).with_style(style))
+            },
+            WidgetKind::Block => Box::new(Block::new(x, y, w, h, event, config).with_style(style)),
+            WidgetKind::TextBlock {text} => Box::new(TextBlock::new(text, x, y, w, h, event, config).with_style(style)),
+            WidgetKind::Image {path, alternate} => {
+                let mut image = GuiImage::new(leak(&path), x, y, w, h, event, config);
+                if let Some(alternate) = alternate {
+                    image = image.with_alternate(leak(&alternate));
+                }
+                Box::new(image.with_style(style))
+            },
+            WidgetKind::Spinner {value, min, max, step, precision, unit} => {
+                Box::new(Spinner::new(value, min, max, step, x, y, w, h, event, config)
+                    .with_format(precision, leak(&unit))
+                    .with_style(style))
+            },
+            WidgetKind::Toggle {checked} => Box::new(Toggle::new(checked, x, y, w, h, event, config).with_style(style)),
+            WidgetKind::Checkbox {checked, text} => Box::new(Checkbox::new(checked, text, x, y, w, h, event, config).with_style(style)),
+            WidgetKind::ProgressBar {value, min, max, orientation, precision, unit} => {
+                Box::new(ProgressBar::new(value, min, max, orientation, x, y, w, h, config)
+                    .with_format(precision, leak(&unit))
+                    .with_style(style))
+            },
+            WidgetKind::Slider {value, min, max, step, orientation, precision} => {
+                Box::new(Slider::new(value, min, max, step, orientation, x, y, w, h, event, config)
+                    .with_precision(precision)
+                    .with_style(style))
+            },
+            WidgetKind::Chart {series, capacity, y_range, grid} => {
+                let series = series.iter().map(|name| leak(name)).collect();
+                let mut chart = Chart::new(series, capacity, x, y, w, h, config);
+                if let Some((min, max)) = y_range {
+                    chart = chart.with_y_range(min, max);
+                }
+                if let Some((rows, columns)) = grid {
+                    chart = chart.with_grid(rows, columns);
+                }
+                Box::new(chart.with_style(style))
+            },
+            WidgetKind::Gauge {value, min, max, precision, unit} => {
+                Box::new(Gauge::new(value, min, max, x, y, w, h, config)
+                    .with_format(precision, leak(&unit), true)
+                    .with_style(style))
+            },
+            WidgetKind::Keyboard {text, max_length} => {
+                let mut keyboard = Keyboard::new(text, x, y, w, h, event, config);
+                if let Some(max_length) = max_length {
+                    keyboard = keyboard.with_max_length(max_length);
+                }
+                Box::new(keyboard.with_style(style))
+            },
+            WidgetKind::Menu {items} => {
+                let items = items.iter().map(|item| {
+                    let name = item.name.as_deref().unwrap_or(&item.text);
+                    MenuItem::new(leak(&item.text), Event::new(leak(&item.event), None), leak(name))
+                }).collect();
+                Box::new(Menu::new(items, x, y, w, h, event, config).with_style(style))
+            },
+            WidgetKind::Hstack(_) | WidgetKind::Vstack(_) | WidgetKind::Grid(_) => unreachable!("layouts are built by into_layout")
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl View {
+    // build a view straight from a toml or json file
+    pub fn from_file(path: &str, update_fn: ViewStateUpdater, state_receiver: Receiver<Vec<u8>>, config: &'static GuiConfig) -> Result<View, Box<dyn Error>> {
+        ViewSpec::from_file(path)?.build(update_fn, state_receiver, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_util;
+
+    #[test]
+    fn missing_images_are_an_error() {
+        let spec = ViewSpec::from_toml("[[widgets]]\ntype = \"image\"\npath = \"no/such/image.png\"").unwrap();
+        assert!(spec.check().is_err());
+    }
+
+    #[test]
+    fn missing_images_inside_layouts_are_an_error() {
+        let spec = ViewSpec::from_toml(
+            "[[widgets]]\ntype = \"vstack\"\nchildren = [{type = \"image\", path = \"assets/lovett.png\", alternate = \"no/such/image.png\"}]"
+        ).unwrap();
+        assert!(spec.check().is_err());
+    }
+
+    #[test]
+    fn existing_images_pass() {
+        let spec = ViewSpec::from_toml("[[widgets]]\ntype = \"image\"\npath = \"assets/lovett.png\"").unwrap();
+        assert!(spec.check().is_ok());
+    }
+
+    #[test]
+    fn undecodable_images_are_an_error() {
+        let spec = ViewSpec::from_toml("[[widgets]]\ntype = \"image\"\npath = \"Cargo.toml\"").unwrap();
+        assert!(spec.check().is_err());
+        assert!(GuiImage::try_new("Cargo.toml", 0, 0, 10, 10, Event::new("", None), test_util::config()).is_err());
+    }
+}